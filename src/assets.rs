@@ -1,10 +1,14 @@
+use crate::game::{BossDefinition, ScoringRules};
+use crate::localization::StringTable;
 use crate::prelude::*;
+use crate::settings::Language;
+use bevy::utils::HashMap;
 use bevy::winit::cursor::{CursorIcon, CustomCursor};
+use enum_iterator::all;
 use iyes_progress::prelude::*;
 
 #[derive(Resource)]
 pub struct ExampleAssets {
-  pub tree: Handle<Image>,
   pub player: Handle<Image>,
 }
 
@@ -15,6 +19,39 @@ pub struct UiAssets {
   pub cursors: Vec<Handle<Image>>,
 }
 
+#[derive(Resource)]
+pub struct AudioAssets {
+  pub menu_theme: Handle<AudioSource>,
+  /// Stems of the fight music, see [`crate::audio::MusicLayer`].
+  pub fight_base: Handle<AudioSource>,
+  pub fight_drums: Handle<AudioSource>,
+  pub fight_lead: Handle<AudioSource>,
+  pub fight_danger: Handle<AudioSource>,
+  pub hit: Handle<AudioSource>,
+  pub dash: Handle<AudioSource>,
+  pub boss_roar: Handle<AudioSource>,
+}
+
+/// Definitions of every boss, in the order they are fought.
+#[derive(Resource)]
+pub struct BossAssets {
+  pub bosses: Vec<Handle<BossDefinition>>,
+}
+
+#[derive(Resource)]
+pub struct ScoringAssets {
+  pub rules: Handle<ScoringRules>,
+}
+
+/// String tables of every [`Language`].
+#[derive(Resource)]
+pub struct LocaleAssets {
+  pub tables: HashMap<Language, Handle<StringTable>>,
+}
+
+const BOSS_DEFINITIONS: [&str; 2] =
+  ["bosses/elder_tree.boss.ron", "bosses/hollow_oak.boss.ron"];
+
 pub struct AssetsLoadingPlugin;
 
 impl Plugin for AssetsLoadingPlugin {
@@ -30,7 +67,14 @@ impl Plugin for AssetsLoadingPlugin {
       )
       .add_systems(
         OnEnter(AppState::AssetsLoading),
-        (load_example_assets, load_ui_assets),
+        (
+          load_example_assets,
+          load_ui_assets,
+          load_audio_assets,
+          load_boss_assets,
+          load_scoring_assets,
+          load_locale_assets,
+        ),
       )
       .add_systems(
         OnExit(AppState::AssetsLoading),
@@ -44,13 +88,11 @@ fn load_example_assets(
   asset_server: Res<AssetServer>,
   mut loading: ResMut<AssetsLoading<AppState>>,
 ) {
-  let tree: Handle<Image> = asset_server.load("tree.png");
   let player: Handle<Image> = asset_server.load("placeholder_char.png");
 
-  loading.add(&tree);
   loading.add(&player);
 
-  commands.insert_resource(ExampleAssets { tree, player });
+  commands.insert_resource(ExampleAssets { player });
 }
 
 fn load_ui_assets(
@@ -76,6 +118,82 @@ fn load_ui_assets(
   });
 }
 
+fn load_audio_assets(
+  mut commands: Commands,
+  asset_server: Res<AssetServer>,
+  mut loading: ResMut<AssetsLoading<AppState>>,
+) {
+  let audio = AudioAssets {
+    menu_theme: asset_server.load("audio/music/menu.wav"),
+    fight_base: asset_server.load("audio/music/fight_base.wav"),
+    fight_drums: asset_server.load("audio/music/fight_drums.wav"),
+    fight_lead: asset_server.load("audio/music/fight_lead.wav"),
+    fight_danger: asset_server.load("audio/music/fight_danger.wav"),
+    hit: asset_server.load("audio/sfx/hit.wav"),
+    dash: asset_server.load("audio/sfx/dash.wav"),
+    boss_roar: asset_server.load("audio/sfx/roar.wav"),
+  };
+
+  loading.add(&audio.menu_theme);
+  loading.add(&audio.fight_base);
+  loading.add(&audio.fight_drums);
+  loading.add(&audio.fight_lead);
+  loading.add(&audio.fight_danger);
+  loading.add(&audio.hit);
+  loading.add(&audio.dash);
+  loading.add(&audio.boss_roar);
+
+  commands.insert_resource(audio);
+}
+
+fn load_boss_assets(
+  mut commands: Commands,
+  asset_server: Res<AssetServer>,
+  mut loading: ResMut<AssetsLoading<AppState>>,
+) {
+  let bosses: Vec<Handle<BossDefinition>> = BOSS_DEFINITIONS
+    .iter()
+    .map(|path| asset_server.load(*path))
+    .collect();
+
+  for boss in &bosses {
+    loading.add(boss);
+  }
+
+  commands.insert_resource(BossAssets { bosses });
+}
+
+fn load_scoring_assets(
+  mut commands: Commands,
+  asset_server: Res<AssetServer>,
+  mut loading: ResMut<AssetsLoading<AppState>>,
+) {
+  let rules: Handle<ScoringRules> = asset_server.load("fight.scoring.ron");
+
+  loading.add(&rules);
+
+  commands.insert_resource(ScoringAssets { rules });
+}
+
+fn load_locale_assets(
+  mut commands: Commands,
+  asset_server: Res<AssetServer>,
+  mut loading: ResMut<AssetsLoading<AppState>>,
+) {
+  let tables: HashMap<Language, Handle<StringTable>> = all::<Language>()
+    .map(|language| {
+      let path = format!("locales/{}.lang.ron", language.code());
+      (language, asset_server.load(path))
+    })
+    .collect();
+
+  for table in tables.values() {
+    loading.add(table);
+  }
+
+  commands.insert_resource(LocaleAssets { tables });
+}
+
 fn add_cursor(
   mut commands: Commands,
   window: Single<Entity, With<Window>>,
@@ -0,0 +1,70 @@
+use bevy::utils::HashMap;
+
+use crate::prelude::*;
+
+use super::music::MusicTrack;
+
+/// Seconds a layer takes to fade between silent and full volume.
+const LAYER_FADE_DURATION: f32 = 2.0;
+
+/// An optional stem of a song, played in sync with the rest of it.
+/// Stems without a layer are always audible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MusicLayer {
+  Drums,
+  Lead,
+  Danger,
+}
+
+/// Volumes the layers of the playing song fade towards, driven by gameplay.
+/// Every layer starts silent.
+#[derive(Resource, Default)]
+pub struct MusicController {
+  levels: HashMap<MusicLayer, f32>,
+}
+
+impl MusicController {
+  /// Fades a layer towards a volume between `0.0` and `1.0`.
+  pub fn set_layer(&mut self, layer: MusicLayer, level: f32) {
+    self.levels.insert(layer, level.clamp(0.0, 1.0));
+  }
+
+  pub fn fade_in(&mut self, layer: MusicLayer) {
+    self.set_layer(layer, 1.0);
+  }
+
+  pub fn fade_out(&mut self, layer: MusicLayer) {
+    self.set_layer(layer, 0.0);
+  }
+
+  /// Silences every layer.
+  pub fn reset(&mut self) {
+    self.levels.clear();
+  }
+
+  fn level(&self, layer: MusicLayer) -> f32 {
+    self.levels.get(&layer).copied().unwrap_or(0.0)
+  }
+}
+
+/// The layer of a [`MusicTrack`] and its current volume.
+#[derive(Component)]
+pub(super) struct MusicStem {
+  pub layer: MusicLayer,
+  pub gain: f32,
+}
+
+/// Layers follow gameplay, so they fade on virtual time and hold still
+/// while the game is paused.
+pub(super) fn fade_layers(
+  time: Res<Time<Virtual>>,
+  controller: Res<MusicController>,
+  mut stems: Query<&mut MusicStem, With<MusicTrack>>,
+) {
+  let step = time.delta_secs() / LAYER_FADE_DURATION;
+
+  for mut stem in &mut stems {
+    let target = controller.level(stem.layer);
+    stem.gain += (target - stem.gain).clamp(-step, step);
+  }
+}
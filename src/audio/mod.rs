@@ -0,0 +1,67 @@
+mod layers;
+mod music;
+mod sfx;
+
+pub use layers::{MusicController, MusicLayer};
+pub use sfx::SoundEffect;
+
+use crate::prelude::*;
+
+/// Volume multiplier of every sound while the game is paused.
+const DUCK_LEVEL: f32 = 0.3;
+/// How fast the ducking reaches its level, per second.
+const DUCK_SPEED: f32 = 4.0;
+
+pub struct GameAudioPlugin;
+
+impl Plugin for GameAudioPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .init_resource::<Ducking>()
+      .init_resource::<MusicController>()
+      .add_event::<SoundEffect>()
+      .add_systems(
+        Update,
+        (
+          update_ducking,
+          music::change_music.run_if(state_changed::<AppState>),
+          (music::fade_music, layers::fade_layers),
+          sfx::play_sound_effects,
+          (
+            music::apply_music_volume,
+            sfx::apply_sound_volume,
+          ),
+        )
+          .chain(),
+      );
+  }
+}
+
+/// Multiplier applied to the volume of every sound, lowered while paused.
+#[derive(Resource, Deref)]
+struct Ducking(f32);
+
+impl Default for Ducking {
+  fn default() -> Self {
+    Self(1.0)
+  }
+}
+
+/// Audio keeps running while [`Time<Virtual>`] is paused, so it is timed
+/// on real time.
+fn update_ducking(
+  time: Res<Time<Real>>,
+  in_game_state: Option<Res<State<InGameState>>>,
+  mut ducking: ResMut<Ducking>,
+) {
+  let paused =
+    in_game_state.is_some_and(|state| *state.get() == InGameState::Paused);
+  let target = if paused { DUCK_LEVEL } else { 1.0 };
+
+  let step = DUCK_SPEED * time.delta_secs();
+  let level = ducking.0 + (target - ducking.0).clamp(-step, step);
+
+  if level != ducking.0 {
+    ducking.0 = level;
+  }
+}
@@ -0,0 +1,144 @@
+use bevy::audio::Volume;
+
+use crate::{assets::AudioAssets, prelude::*, settings::Settings};
+
+use super::{
+  layers::{MusicLayer, MusicStem},
+  Ducking,
+};
+
+/// Seconds a track takes to fade in or out when the music changes.
+const CROSSFADE_DURATION: f32 = 1.5;
+
+/// A playing music track, faded in and out when the music changes.
+#[derive(Component)]
+pub(super) struct MusicTrack {
+  song: Song,
+  /// Current volume of the fade, between `0.0` and `1.0`.
+  fade: f32,
+  /// Volume the fade moves towards. Tracks fading to `0.0` are despawned.
+  target: f32,
+}
+
+/// A piece of music, made of one or more stems that loop together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Song {
+  Menu,
+  Fight,
+}
+
+impl Song {
+  /// The stems of the song, with the layer each one belongs to.
+  fn stems(
+    self,
+    audio: &AudioAssets,
+  ) -> Vec<(Handle<AudioSource>, Option<MusicLayer>)> {
+    match self {
+      Song::Menu => vec![(audio.menu_theme.clone(), None)],
+      Song::Fight => vec![
+        (audio.fight_base.clone(), None),
+        (
+          audio.fight_drums.clone(),
+          Some(MusicLayer::Drums),
+        ),
+        (
+          audio.fight_lead.clone(),
+          Some(MusicLayer::Lead),
+        ),
+        (
+          audio.fight_danger.clone(),
+          Some(MusicLayer::Danger),
+        ),
+      ],
+    }
+  }
+}
+
+/// The song played in each state, if any.
+fn music_for(state: &AppState) -> Option<Song> {
+  match state {
+    AppState::AssetsLoading => None,
+    AppState::MainMenu | AppState::GameOver | AppState::Victory => {
+      Some(Song::Menu)
+    }
+    AppState::InGame => Some(Song::Fight),
+  }
+}
+
+/// Crossfades to the song of the new [`AppState`], unless it is already
+/// playing.
+pub(super) fn change_music(
+  mut commands: Commands,
+  state: Res<State<AppState>>,
+  audio: Res<AudioAssets>,
+  mut tracks: Query<&mut MusicTrack>,
+) {
+  let next = music_for(state.get());
+  let mut playing = false;
+
+  for mut track in &mut tracks {
+    if Some(track.song) == next {
+      track.target = 1.0;
+      playing = true;
+    } else {
+      track.target = 0.0;
+    }
+  }
+
+  let Some(song) = next else {
+    return;
+  };
+
+  if playing {
+    return;
+  }
+
+  // Stems are spawned in the same frame, so they start playing together.
+  for (source, layer) in song.stems(&audio) {
+    let mut track = commands.spawn((
+      Name::new("MusicTrack"),
+      MusicTrack {
+        song,
+        fade: 0.0,
+        target: 1.0,
+      },
+      AudioPlayer(source),
+      PlaybackSettings::LOOP.with_volume(Volume::new(0.0)),
+    ));
+
+    if let Some(layer) = layer {
+      track.insert(MusicStem { layer, gain: 0.0 });
+    }
+  }
+}
+
+pub(super) fn fade_music(
+  mut commands: Commands,
+  time: Res<Time<Real>>,
+  mut tracks: Query<(Entity, &mut MusicTrack)>,
+) {
+  let step = time.delta_secs() / CROSSFADE_DURATION;
+
+  for (entity, mut track) in &mut tracks {
+    track.fade += (track.target - track.fade).clamp(-step, step);
+
+    if track.fade <= 0.0 && track.target <= 0.0 {
+      commands.entity(entity).despawn();
+    }
+  }
+}
+
+pub(super) fn apply_music_volume(
+  settings: Res<Settings>,
+  ducking: Res<Ducking>,
+  tracks: Query<(
+    &MusicTrack,
+    Option<&MusicStem>,
+    &AudioSink,
+  )>,
+) {
+  for (track, stem, sink) in &tracks {
+    let gain = stem.map_or(1.0, |stem| stem.gain);
+    sink.set_volume(settings.music_level * track.fade * gain * **ducking);
+  }
+}
@@ -0,0 +1,62 @@
+use bevy::audio::Volume;
+use bevy::utils::HashSet;
+
+use crate::{assets::AudioAssets, prelude::*, settings::Settings};
+
+use super::Ducking;
+
+/// An event sent to play a sound effect.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundEffect {
+  Hit,
+  Dash,
+  BossRoar,
+}
+
+impl SoundEffect {
+  fn source(self, audio: &AudioAssets) -> Handle<AudioSource> {
+    match self {
+      Self::Hit => audio.hit.clone(),
+      Self::Dash => audio.dash.clone(),
+      Self::BossRoar => audio.boss_roar.clone(),
+    }
+  }
+}
+
+/// A playing sound effect, despawned once finished.
+#[derive(Component)]
+pub(super) struct SoundEffectPlayer;
+
+pub(super) fn play_sound_effects(
+  mut commands: Commands,
+  mut sound_effects: EventReader<SoundEffect>,
+  audio: Res<AudioAssets>,
+  settings: Res<Settings>,
+  ducking: Res<Ducking>,
+) {
+  // The same effect sent several times in a frame, e.g. a hit from many
+  // projectiles, only plays once.
+  let sound_effects = sound_effects.read().copied().collect::<HashSet<_>>();
+
+  for sound_effect in sound_effects {
+    commands.spawn((
+      Name::new("SoundEffect"),
+      SoundEffectPlayer,
+      AudioPlayer(sound_effect.source(&audio)),
+      PlaybackSettings::DESPAWN.with_volume(Volume::new(
+        settings.sound_level * **ducking,
+      )),
+    ));
+  }
+}
+
+/// Keeps playing sound effects in sync with the settings.
+pub(super) fn apply_sound_volume(
+  settings: Res<Settings>,
+  ducking: Res<Ducking>,
+  players: Query<&AudioSink, With<SoundEffectPlayer>>,
+) {
+  for sink in &players {
+    sink.set_volume(settings.sound_level * **ducking);
+  }
+}
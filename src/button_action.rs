@@ -0,0 +1,52 @@
+use bevy::state::state::FreelyMutableState;
+
+use crate::prelude::*;
+
+pub struct ButtonActionPlugin;
+
+impl Plugin for ButtonActionPlugin {
+  fn build(&self, app: &mut App) {
+    app.add_systems(Update, run_button_actions);
+  }
+}
+
+/// What happens when a [`Button`] is pressed, so screens only need to
+/// spawn their buttons.
+#[derive(Component)]
+pub struct ButtonAction(Box<dyn Fn(&mut Commands) + Send + Sync>);
+
+impl ButtonAction {
+  /// Transitions to a state, of a [`States`] or [`SubStates`] type.
+  pub fn set_state<S: FreelyMutableState>(state: S) -> Self {
+    Self(Box::new(move |commands| {
+      commands.set_state(state.clone());
+    }))
+  }
+
+  /// Runs a system, which can't capture anything.
+  pub fn run_system<M: 'static>(
+    system: impl IntoSystem<(), (), M> + Clone + Send + Sync + 'static,
+  ) -> Self {
+    Self(Box::new(move |commands| {
+      commands.run_system_cached(system.clone());
+    }))
+  }
+
+  /// Sends an event.
+  pub fn send_event<E: Event + Clone>(event: E) -> Self {
+    Self(Box::new(move |commands| {
+      commands.send_event(event.clone());
+    }))
+  }
+}
+
+fn run_button_actions(
+  mut commands: Commands,
+  buttons: Query<(&Interaction, &ButtonAction), Changed<Interaction>>,
+) {
+  for (interaction, action) in &buttons {
+    if interaction == &Interaction::Pressed {
+      (action.0)(&mut commands);
+    }
+  }
+}
@@ -69,6 +69,7 @@ impl Plugin for DevToolsPlugin {
           log_transitions::<AppState>,
           log_transitions::<MainMenuState>,
           log_transitions::<InGameState>,
+          log_transitions::<FightState>,
         ),
       )
       .register_type::<Option<Handle<Image>>>()
@@ -0,0 +1,124 @@
+use enum_iterator::Sequence;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+/// Seconds added to the invulnerability after a hit by
+/// [`Assists::extra_invulnerability`].
+const EXTRA_INVULNERABILITY: f32 = 0.5;
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .register_type::<Difficulty>()
+      .init_resource::<Difficulty>();
+  }
+}
+
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+  Hash,
+  Reflect,
+  Sequence,
+  Serialize,
+  Deserialize
+)]
+pub enum DifficultyLevel {
+  Story,
+  #[default]
+  Normal,
+  Hard,
+}
+
+impl DifficultyLevel {
+  pub fn text_key(self) -> &'static str {
+    match self {
+      Self::Story => "difficulty.story",
+      Self::Normal => "difficulty.normal",
+      Self::Hard => "difficulty.hard",
+    }
+  }
+}
+
+/// Optional help on top of the [`DifficultyLevel`].
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct Assists {
+  /// Multiplier of the damage taken by the player.
+  pub damage_taken: f32,
+  pub extra_invulnerability: bool,
+  /// Dashes don't have a cooldown.
+  pub infinite_dash: bool,
+  /// The player can't go below one hit point.
+  pub no_fail: bool,
+}
+
+impl Assists {
+  /// Whether any assist differs from the default.
+  pub fn any(&self) -> bool {
+    *self != Self::default()
+  }
+}
+
+impl Default for Assists {
+  fn default() -> Self {
+    Self {
+      damage_taken: 1.0,
+      extra_invulnerability: false,
+      infinite_dash: false,
+      no_fail: false,
+    }
+  }
+}
+
+/// The difficulty of the next run, chosen from the main menu before it
+/// starts. Combat systems read their multipliers from it.
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource)]
+pub struct Difficulty {
+  pub level: DifficultyLevel,
+  pub assists: Assists,
+}
+
+impl Difficulty {
+  pub fn boss_health(&self) -> f32 {
+    match self.level {
+      DifficultyLevel::Story => 0.6,
+      DifficultyLevel::Normal => 1.0,
+      DifficultyLevel::Hard => 1.4,
+    }
+  }
+
+  pub fn projectile_speed(&self) -> f32 {
+    match self.level {
+      DifficultyLevel::Story => 0.75,
+      DifficultyLevel::Normal => 1.0,
+      DifficultyLevel::Hard => 1.2,
+    }
+  }
+
+  /// Multiplier of the damage taken by the player, assists included.
+  pub fn damage_taken(&self) -> f32 {
+    let level = match self.level {
+      DifficultyLevel::Story => 0.5,
+      DifficultyLevel::Normal => 1.0,
+      DifficultyLevel::Hard => 1.5,
+    };
+
+    level * self.assists.damage_taken
+  }
+
+  /// Seconds added to the invulnerability of the player after a hit.
+  pub fn extra_invulnerability(&self) -> f32 {
+    match self.assists.extra_invulnerability {
+      true => EXTRA_INVULNERABILITY,
+      false => 0.0,
+    }
+  }
+}
@@ -0,0 +1,32 @@
+use std::time::{Duration, Instant};
+
+use crate::{prelude::*, settings::Settings};
+
+pub struct FrameLimiterPlugin;
+
+impl Plugin for FrameLimiterPlugin {
+  fn build(&self, app: &mut App) {
+    app.add_systems(Last, limit_frame_rate);
+  }
+}
+
+/// Waits at the end of the frame until [`Settings::frame_cap`] allows the
+/// next one to start.
+fn limit_frame_rate(
+  settings: Res<Settings>,
+  mut last_frame_end: Local<Option<Instant>>,
+) {
+  if let (Some(fps), Some(last_frame_end)) = (
+    settings.frame_cap.fps(),
+    *last_frame_end,
+  ) {
+    let frame_time = Duration::from_secs_f64(1.0 / f64::from(fps));
+    let elapsed = last_frame_end.elapsed();
+
+    if elapsed < frame_time {
+      std::thread::sleep(frame_time - elapsed);
+    }
+  }
+
+  *last_frame_end = Some(Instant::now());
+}
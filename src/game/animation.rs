@@ -0,0 +1,124 @@
+use crate::prelude::*;
+
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+  fn build(&self, app: &mut App) {
+    app.add_systems(
+      Update,
+      animate_sprite.run_if(in_state(InGameState::Running)),
+    );
+  }
+}
+
+/// A sequence of texture atlas indices played at a fixed rate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationClip {
+  pub frames: Vec<usize>,
+  /// Seconds each frame stays on screen, positive. Boss definitions reject
+  /// other values.
+  pub frame_duration: f32,
+  /// Whether the clip starts over once the last frame is reached.
+  pub looping: bool,
+}
+
+impl AnimationClip {
+  pub fn new(frames: Vec<usize>, frame_duration: f32) -> Self {
+    Self {
+      frames,
+      frame_duration,
+      looping: true,
+    }
+  }
+
+  /// A looping clip playing every index from `first` to `last`.
+  pub fn range(first: usize, last: usize, frame_duration: f32) -> Self {
+    Self::new((first..=last).collect(), frame_duration)
+  }
+
+  /// Stops the clip on its last frame instead of looping.
+  pub fn once(mut self) -> Self {
+    self.looping = false;
+    self
+  }
+}
+
+/// Plays an [`AnimationClip`] on the texture atlas of the entity [`Sprite`].
+#[derive(Component)]
+pub struct SpriteAnimation {
+  clip: AnimationClip,
+  frame: usize,
+  timer: Timer,
+}
+
+impl SpriteAnimation {
+  pub fn new(clip: AnimationClip) -> Self {
+    let timer = Timer::from_seconds(
+      clip.frame_duration,
+      TimerMode::Repeating,
+    );
+
+    Self {
+      clip,
+      frame: 0,
+      timer,
+    }
+  }
+
+  /// Switches to another clip, starting it from its first frame.
+  /// Playing the clip that is already running does nothing.
+  pub fn play(&mut self, clip: AnimationClip) {
+    if self.clip != clip {
+      *self = Self::new(clip);
+    }
+  }
+
+  /// Whether a clip that doesn't loop has reached its last frame.
+  pub fn is_finished(&self) -> bool {
+    !self.clip.looping && self.frame + 1 >= self.clip.frames.len()
+  }
+
+  fn index(&self) -> Option<usize> {
+    self.clip.frames.get(self.frame).copied()
+  }
+}
+
+/// An animation without frames, which leaves the sprite untouched until
+/// a clip is played.
+impl Default for SpriteAnimation {
+  fn default() -> Self {
+    Self::new(AnimationClip::new(Vec::new(), 1.0))
+  }
+}
+
+fn animate_sprite(
+  time: Res<Time>,
+  mut query: Query<(&mut SpriteAnimation, &mut Sprite)>,
+) {
+  for (mut animation, mut sprite) in &mut query {
+    let frames = animation.clip.frames.len();
+
+    let steps = animation
+      .timer
+      .tick(time.delta())
+      .times_finished_this_tick();
+
+    for _ in 0..steps {
+      if animation.frame + 1 < frames {
+        animation.frame += 1;
+      } else if animation.clip.looping {
+        animation.frame = 0;
+      }
+    }
+
+    let Some(index) = animation.index() else {
+      continue;
+    };
+
+    if let Some(atlas) = &mut sprite.texture_atlas {
+      if atlas.index != index {
+        atlas.index = index;
+      }
+    }
+  }
+}
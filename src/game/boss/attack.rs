@@ -0,0 +1,161 @@
+use serde::Deserialize;
+
+use crate::game::combat::Faction;
+use crate::game::player::Player;
+use crate::game::projectile::{BulletPattern, ProjectileEmitter};
+use crate::game::telegraph::{Telegraph, TelegraphAnchor, TelegraphAttack};
+use crate::prelude::*;
+
+use super::BossPhases;
+
+/// Delay before a boss starts attacking once it has been spawned.
+const FIRST_ATTACK_DELAY: f32 = 1.5;
+
+/// A single entry of a boss attack list.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AttackDefinition {
+  pub name: String,
+  /// Seconds to wait after this attack before starting the next one.
+  pub cooldown: f32,
+  /// Indices of the phases the attack is used in, every phase if empty.
+  #[serde(default)]
+  pub phases: Vec<usize>,
+  /// Projectiles fired by the boss during the attack.
+  #[serde(default)]
+  pub pattern: Option<BulletPattern>,
+  /// Area hit announced by a telegraph on the arena floor.
+  #[serde(default)]
+  pub telegraph: Option<TelegraphAttack>,
+}
+
+impl AttackDefinition {
+  fn available_in(&self, phase: usize) -> bool {
+    self.phases.is_empty() || self.phases.contains(&phase)
+  }
+}
+
+/// The attack list of a boss, cycled in order.
+#[derive(Component)]
+pub struct BossAttacks {
+  pub attacks: Vec<AttackDefinition>,
+  timer: Timer,
+  next: usize,
+}
+
+impl BossAttacks {
+  pub fn new(attacks: Vec<AttackDefinition>) -> Self {
+    Self {
+      attacks,
+      timer: Timer::from_seconds(FIRST_ATTACK_DELAY, TimerMode::Once),
+      next: 0,
+    }
+  }
+}
+
+/// An event sent when a boss starts one of its [`BossAttacks`].
+#[derive(Event)]
+pub struct BossAttackEvent {
+  pub boss: Entity,
+  pub attack: AttackDefinition,
+}
+
+/// Starts the next attack available in the current phase once the
+/// cooldown of the previous one has elapsed.
+pub(super) fn schedule_boss_attacks(
+  time: Res<Time>,
+  mut query: Query<(Entity, &mut BossAttacks, &BossPhases)>,
+  mut attack_events: EventWriter<BossAttackEvent>,
+) {
+  for (boss, mut attacks, phases) in &mut query {
+    attacks.timer.tick(time.delta());
+
+    if !attacks.timer.finished() {
+      continue;
+    }
+
+    let phase = phases.current.unwrap_or_default();
+    let count = attacks.attacks.len();
+
+    let Some(index) = (0..count)
+      .map(|offset| (attacks.next + offset) % count)
+      .find(|&index| attacks.attacks[index].available_in(phase))
+    else {
+      continue;
+    };
+
+    let attack = attacks.attacks[index].clone();
+
+    attacks.next = (index + 1) % count;
+    attacks.timer = Timer::from_seconds(attack.cooldown, TimerMode::Once);
+
+    debug!("boss attack: {}", attack.name);
+    attack_events.send(BossAttackEvent { boss, attack });
+  }
+}
+
+/// Starts the bullet pattern of an attack from the boss position.
+pub(super) fn fire_attack_patterns(
+  mut commands: Commands,
+  mut attack_events: EventReader<BossAttackEvent>,
+) {
+  for BossAttackEvent { boss, attack } in attack_events.read() {
+    if let Some(pattern) = &attack.pattern {
+      commands.entity(*boss).insert(ProjectileEmitter::new(
+        pattern.clone(),
+        Faction::Boss,
+      ));
+    }
+  }
+}
+
+/// Depth of telegraphs, below every other sprite of the arena.
+const TELEGRAPH_DEPTH: f32 = -1.0;
+
+/// Places the telegraph of an attack, facing from the boss to the player.
+pub(super) fn place_attack_telegraphs(
+  mut commands: Commands,
+  mut attack_events: EventReader<BossAttackEvent>,
+  transforms: Query<&GlobalTransform>,
+  player: Query<Entity, With<Player>>,
+) {
+  let player_position = player
+    .get_single()
+    .ok()
+    .and_then(|player| transforms.get(player).ok())
+    .map(|transform| transform.translation().truncate());
+
+  for BossAttackEvent { boss, attack } in attack_events.read() {
+    let Some(telegraph) = &attack.telegraph else {
+      continue;
+    };
+
+    let Ok(boss_transform) = transforms.get(*boss) else {
+      continue;
+    };
+
+    let boss_position = boss_transform.translation().truncate();
+    let target = player_position.unwrap_or(boss_position);
+
+    let position = match telegraph.anchor {
+      TelegraphAnchor::Boss => boss_position,
+      TelegraphAnchor::Player => target,
+    };
+    let direction = (target - boss_position).normalize_or(Vec2::NEG_X);
+
+    commands.spawn((
+      Name::new("Telegraph"),
+      Telegraph::new(
+        telegraph.shape.clone(),
+        telegraph.windup,
+        telegraph.damage,
+        Faction::Boss,
+      ),
+      StateScoped(AppState::InGame),
+      Transform::from_translation(position.extend(TELEGRAPH_DEPTH))
+        .with_rotation(Quat::from_rotation_z(
+          direction.to_angle(),
+        )),
+      Visibility::default(),
+    ));
+  }
+}
@@ -0,0 +1,390 @@
+use avian2d::prelude::*;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::prelude::*;
+
+use super::attack::AttackDefinition;
+use super::BossPhase;
+use crate::game::animation::{AnimationClip, SpriteAnimation};
+
+/// A boss described by a `.boss.ron` file under `assets/bosses/`.
+#[derive(Asset, TypePath, Debug)]
+pub struct BossDefinition {
+  pub name: String,
+  pub health: f32,
+  pub phases: Vec<BossPhase>,
+  #[dependency]
+  pub image: Handle<Image>,
+  pub layout: Handle<TextureAtlasLayout>,
+  pub animation: (usize, usize),
+  pub frame_duration: f32,
+  pub collider: ColliderShape,
+  pub attacks: Vec<AttackDefinition>,
+}
+
+impl BossDefinition {
+  /// The sprite, animation and collider of the boss.
+  pub fn visuals(&self) -> impl Bundle {
+    let (first, last) = self.animation;
+
+    (
+      Sprite::from_atlas_image(
+        self.image.clone(),
+        TextureAtlas {
+          layout: self.layout.clone(),
+          index: first,
+        },
+      ),
+      SpriteAnimation::new(AnimationClip::range(
+        first,
+        last,
+        self.frame_duration,
+      )),
+      self.collider.collider(),
+    )
+  }
+}
+
+/// The shape of a boss collider, as written in a boss definition.
+#[derive(Debug, Clone, Deserialize)]
+pub enum ColliderShape {
+  Rectangle { width: f32, height: f32 },
+  Circle { radius: f32 },
+  Capsule { radius: f32, length: f32 },
+}
+
+impl ColliderShape {
+  pub fn collider(&self) -> Collider {
+    match *self {
+      Self::Rectangle { width, height } => Collider::rectangle(width, height),
+      Self::Circle { radius } => Collider::circle(radius),
+      Self::Capsule { radius, length } => Collider::capsule(radius, length),
+    }
+  }
+}
+
+/// The on-disk layout of a [`BossDefinition`].
+#[derive(Deserialize)]
+struct BossDefinitionFile {
+  name: String,
+  health: f32,
+  phases: Vec<BossPhase>,
+  sprite: SpriteSheetFile,
+  collider: ColliderShape,
+  #[serde(default)]
+  attacks: Vec<AttackDefinition>,
+}
+
+impl BossDefinitionFile {
+  /// Rejects the values the fight can't run with, so a bad definition is
+  /// reported when it loads instead of breaking the fight.
+  fn validate(&self) -> Result<(), BossDefinitionLoaderError> {
+    if self.phases.is_empty() {
+      return Err(BossDefinitionLoaderError::NoPhases);
+    }
+
+    let thresholds_valid = self
+      .phases
+      .iter()
+      .all(|phase| phase.threshold > 0.0 && phase.threshold <= 1.0)
+      && self
+        .phases
+        .windows(2)
+        .all(|pair| pair[0].threshold > pair[1].threshold);
+
+    if !thresholds_valid {
+      return Err(BossDefinitionLoaderError::PhaseThresholds);
+    }
+
+    if let Some(attack) = self.attacks.iter().find(|attack| {
+      attack
+        .pattern
+        .as_ref()
+        .is_some_and(|pattern| pattern.count == 0)
+    }) {
+      return Err(BossDefinitionLoaderError::EmptyPattern(
+        attack.name.clone(),
+      ));
+    }
+
+    let frame_duration = self.sprite.frame_duration;
+    if !(frame_duration > 0.0 && frame_duration.is_finite()) {
+      return Err(BossDefinitionLoaderError::InvalidFrameDuration);
+    }
+
+    if let Some(attack) = self.attacks.iter().find(|attack| {
+      attack.pattern.as_ref().is_some_and(|pattern| {
+        !(pattern.interval > 0.0
+          && pattern.interval.is_finite()
+          && pattern.speed.is_finite())
+      })
+    }) {
+      return Err(
+        BossDefinitionLoaderError::InvalidPattern(attack.name.clone()),
+      );
+    }
+
+    if let Some(attack) = self.attacks.iter().find(|attack| {
+      attack
+        .telegraph
+        .as_ref()
+        .is_some_and(|telegraph| !valid_duration(telegraph.windup))
+    }) {
+      return Err(
+        BossDefinitionLoaderError::InvalidWindup(attack.name.clone()),
+      );
+    }
+
+    if let Some(attack) = self
+      .attacks
+      .iter()
+      .find(|attack| !valid_duration(attack.cooldown))
+    {
+      return Err(
+        BossDefinitionLoaderError::InvalidCooldown(attack.name.clone()),
+      );
+    }
+
+    Ok(())
+  }
+}
+
+/// Whether a number of seconds read from a definition can start a timer.
+fn valid_duration(seconds: f32) -> bool {
+  seconds.is_finite() && seconds >= 0.0
+}
+
+#[derive(Deserialize)]
+struct SpriteSheetFile {
+  /// Path of the sprite sheet, relative to the `assets` folder.
+  image: String,
+  tile_size: (u32, u32),
+  columns: u32,
+  rows: u32,
+  /// First and last atlas index of the idle animation.
+  animation: (usize, usize),
+  frame_duration: f32,
+}
+
+#[derive(Default)]
+pub struct BossDefinitionLoader;
+
+#[derive(Debug, Error)]
+pub enum BossDefinitionLoaderError {
+  #[error("could not read boss definition: {0}")]
+  Io(#[from] std::io::Error),
+  #[error("could not parse boss definition: {0}")]
+  Ron(#[from] ron::error::SpannedError),
+  #[error("boss definition has no phases")]
+  NoPhases,
+  #[error("phase thresholds must be between 0 and 1, in descending order")]
+  PhaseThresholds,
+  #[error("frame duration must be positive and finite")]
+  InvalidFrameDuration,
+  #[error("pattern of attack {0} fires no projectiles")]
+  EmptyPattern(String),
+  #[error("cooldown of attack {0} must be a finite, non-negative duration")]
+  InvalidCooldown(String),
+  #[error("windup of attack {0} must be a finite, non-negative duration")]
+  InvalidWindup(String),
+  #[error(
+    "pattern of attack {0} needs a positive interval and a finite speed"
+  )]
+  InvalidPattern(String),
+}
+
+impl AssetLoader for BossDefinitionLoader {
+  type Asset = BossDefinition;
+  type Settings = ();
+  type Error = BossDefinitionLoaderError;
+
+  async fn load(
+    &self,
+    reader: &mut dyn Reader,
+    _settings: &(),
+    load_context: &mut LoadContext<'_>,
+  ) -> Result<Self::Asset, Self::Error> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).await?;
+
+    let file: BossDefinitionFile = ron::de::from_bytes(&bytes)?;
+    file.validate()?;
+
+    let SpriteSheetFile {
+      image,
+      tile_size: (width, height),
+      columns,
+      rows,
+      animation,
+      frame_duration,
+    } = file.sprite;
+
+    let layout = TextureAtlasLayout::from_grid(
+      UVec2::new(width, height),
+      columns,
+      rows,
+      None,
+      None,
+    );
+
+    Ok(BossDefinition {
+      name: file.name,
+      health: file.health,
+      phases: file.phases,
+      image: load_context.load(image),
+      layout: load_context.add_labeled_asset("layout".to_string(), layout),
+      animation,
+      frame_duration,
+      collider: file.collider,
+      attacks: file.attacks,
+    })
+  }
+
+  fn extensions(&self) -> &[&str] {
+    &["boss.ron"]
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// A valid definition with `attacks` spliced in.
+  fn definition(attacks: &str) -> BossDefinitionFile {
+    animated_definition(attacks, "0.1")
+  }
+
+  fn animated_definition(
+    attacks: &str,
+    frame_duration: &str,
+  ) -> BossDefinitionFile {
+    let contents = format!(
+      r#"(
+        name: "Test",
+        health: 10.0,
+        phases: [(name: "One", threshold: 1.0), (name: "Two", threshold: 0.5)],
+        sprite: (
+          image: "test.png",
+          tile_size: (16, 16),
+          columns: 1,
+          rows: 1,
+          animation: (0, 0),
+          frame_duration: {frame_duration},
+        ),
+        collider: Circle(radius: 8.0),
+        attacks: [{attacks}],
+      )"#
+    );
+
+    ron::de::from_str(&contents).expect("definition should parse")
+  }
+
+  #[test]
+  fn shipped_definitions_are_valid() {
+    for contents in [
+      include_str!("../../../assets/bosses/elder_tree.boss.ron"),
+      include_str!("../../../assets/bosses/hollow_oak.boss.ron"),
+    ] {
+      let file: BossDefinitionFile =
+        ron::de::from_str(contents).expect("definition should parse");
+      assert!(file.validate().is_ok());
+    }
+  }
+
+  #[test]
+  fn rejects_phases_out_of_order() {
+    let mut file = definition("");
+    file.phases.reverse();
+
+    assert!(matches!(
+      file.validate(),
+      Err(BossDefinitionLoaderError::PhaseThresholds)
+    ));
+  }
+
+  #[test]
+  fn rejects_empty_patterns() {
+    let file = definition(
+      r#"(
+        name: "Nothing",
+        cooldown: 1.0,
+        pattern: Some((kind: Radial, count: 0, speed: 100.0, interval: 0.5)),
+      )"#,
+    );
+
+    assert!(matches!(
+      file.validate(),
+      Err(BossDefinitionLoaderError::EmptyPattern(
+        _
+      ))
+    ));
+  }
+
+  #[test]
+  fn rejects_invalid_cooldowns() {
+    for cooldown in ["-1.0", "NaN", "inf"] {
+      let file = definition(&format!(
+        r#"(name: "Wait", cooldown: {cooldown})"#
+      ));
+
+      assert!(matches!(
+        file.validate(),
+        Err(BossDefinitionLoaderError::InvalidCooldown(_))
+      ));
+    }
+  }
+
+  #[test]
+  fn rejects_invalid_patterns() {
+    for (interval, speed) in [
+      ("0.0", "100.0"),
+      ("-0.5", "100.0"),
+      ("NaN", "100.0"),
+      ("0.5", "inf"),
+    ] {
+      let file = definition(&format!(
+        r#"(
+          name: "Volley",
+          cooldown: 1.0,
+          pattern: Some((kind: Radial, count: 4, speed: {speed}, interval: {interval})),
+        )"#
+      ));
+
+      assert!(matches!(
+        file.validate(),
+        Err(BossDefinitionLoaderError::InvalidPattern(_))
+      ));
+    }
+  }
+
+  #[test]
+  fn rejects_invalid_windups() {
+    for windup in ["-1.0", "NaN"] {
+      let file = definition(&format!(
+        r#"(
+          name: "Slam",
+          cooldown: 1.0,
+          telegraph: Some((shape: Circle(radius: 40.0), windup: {windup})),
+        )"#
+      ));
+
+      assert!(matches!(
+        file.validate(),
+        Err(BossDefinitionLoaderError::InvalidWindup(_))
+      ));
+    }
+  }
+
+  #[test]
+  fn rejects_invalid_frame_durations() {
+    for frame_duration in ["0.0", "-0.1", "NaN"] {
+      assert!(matches!(
+        animated_definition("", frame_duration).validate(),
+        Err(BossDefinitionLoaderError::InvalidFrameDuration)
+      ));
+    }
+  }
+}
@@ -0,0 +1,286 @@
+mod attack;
+mod definition;
+
+use avian2d::prelude::*;
+use serde::Deserialize;
+
+use crate::{audio::SoundEffect, difficulty::Difficulty, prelude::*};
+
+use super::combat::{Faction, Health};
+use super::rush::BossRush;
+use super::screen_shake::ScreenShake;
+use attack::{
+  fire_attack_patterns, place_attack_telegraphs, schedule_boss_attacks,
+  BossAttackEvent, BossAttacks,
+};
+pub use definition::BossDefinition;
+use definition::BossDefinitionLoader;
+
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .init_asset::<BossDefinition>()
+      .init_asset_loader::<BossDefinitionLoader>()
+      .add_event::<BossPhaseEntered>()
+      .add_event::<BossPhaseExited>()
+      .add_event::<BossAttackEvent>()
+      .add_systems(OnEnter(FightState::Intro), spawn_boss)
+      .add_systems(
+        Update,
+        (
+          update_boss_phase,
+          (log_boss_phase, roar_on_new_phase),
+          (
+            schedule_boss_attacks,
+            fire_attack_patterns,
+            place_attack_telegraphs,
+          )
+            .chain()
+            .run_if(in_state(FightState::Fighting)),
+        )
+          .chain()
+          .run_if(in_state(InGameState::Running)),
+      );
+
+    #[cfg(feature = "dev")]
+    app.add_systems(Update, reload_boss_definitions);
+  }
+}
+
+/// Marker for the boss of the current fight.
+#[derive(Component)]
+pub struct Boss {
+  pub name: String,
+}
+
+/// The definition a boss has been spawned from.
+#[derive(Component)]
+pub struct BossDefinitionHandle(
+  #[cfg_attr(not(feature = "dev"), allow(unused))] pub Handle<BossDefinition>,
+);
+
+/// A single phase of a boss fight.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BossPhase {
+  pub name: String,
+  /// The phase starts once the boss [`Health::fraction`] drops to this value.
+  pub threshold: f32,
+}
+
+/// The phases of a boss, ordered from the first to the last one.
+#[derive(Component)]
+pub struct BossPhases {
+  pub phases: Vec<BossPhase>,
+  /// Index of the active phase, `None` until the fight starts.
+  pub current: Option<usize>,
+}
+
+impl BossPhases {
+  pub fn new(phases: Vec<BossPhase>) -> Self {
+    Self {
+      phases,
+      current: None,
+    }
+  }
+
+  /// Index of the phase matching the given health fraction.
+  fn phase_for(&self, fraction: f32) -> usize {
+    self
+      .phases
+      .iter()
+      .rposition(|phase| fraction <= phase.threshold)
+      .unwrap_or(0)
+  }
+}
+
+/// An event sent when a boss enters one of its [`BossPhases`].
+#[derive(Event)]
+pub struct BossPhaseEntered {
+  pub boss: Entity,
+  pub phase: usize,
+}
+
+/// An event sent when a boss leaves one of its [`BossPhases`].
+#[derive(Event)]
+pub struct BossPhaseExited {
+  pub boss: Entity,
+  pub phase: usize,
+}
+
+fn spawn_boss(
+  mut commands: Commands,
+  rush: Res<BossRush>,
+  difficulty: Res<Difficulty>,
+  definitions: Res<Assets<BossDefinition>>,
+) {
+  let Some(handle) = rush.current_boss() else {
+    return;
+  };
+
+  let Some(definition) = definitions.get(&handle) else {
+    error!("boss definition is not loaded");
+    return;
+  };
+
+  commands.spawn((
+    Name::new("Boss"),
+    Boss {
+      name: definition.name.clone(),
+    },
+    BossPhases::new(definition.phases.clone()),
+    BossAttacks::new(definition.attacks.clone()),
+    Faction::Boss,
+    Health::new(definition.health * difficulty.boss_health()),
+    StateScoped(AppState::InGame),
+    Transform::from_xyz(250., 0., 0.),
+    RigidBody::Kinematic,
+    Friction::ZERO.with_combine_rule(CoefficientCombine::Min),
+    Restitution::ZERO.with_combine_rule(CoefficientCombine::Min),
+    definition.visuals(),
+    BossDefinitionHandle(handle),
+  ));
+}
+
+/// Applies edits of a boss definition file to the running fight.
+#[cfg(feature = "dev")]
+fn reload_boss_definitions(
+  mut commands: Commands,
+  mut asset_events: EventReader<AssetEvent<BossDefinition>>,
+  definitions: Res<Assets<BossDefinition>>,
+  difficulty: Res<Difficulty>,
+  mut bosses: Query<(
+    Entity,
+    &BossDefinitionHandle,
+    &mut Boss,
+    &mut Health,
+    &mut BossPhases,
+    &mut BossAttacks,
+  )>,
+) {
+  for event in asset_events.read() {
+    let AssetEvent::Modified { id } = event else {
+      continue;
+    };
+
+    let Some(definition) = definitions.get(*id) else {
+      continue;
+    };
+
+    for (entity, handle, mut boss, mut health, mut phases, mut attacks) in
+      &mut bosses
+    {
+      if handle.0.id() != *id {
+        continue;
+      }
+
+      info!("reloading boss {}", definition.name);
+
+      boss.name = definition.name.clone();
+
+      // Keep the progress of the fight, only rescale it to the new values.
+      let fraction = health.fraction();
+      health.max = definition.health * difficulty.boss_health();
+      health.current = health.max * fraction;
+
+      let last_phase = definition.phases.len().saturating_sub(1);
+      phases.phases = definition.phases.clone();
+      phases.current = phases.current.map(|phase| phase.min(last_phase));
+
+      attacks.attacks = definition.attacks.clone();
+
+      commands.entity(entity).insert(definition.visuals());
+    }
+  }
+}
+
+/// Moves bosses through their [`BossPhases`] as their health drops.
+fn update_boss_phase(
+  mut query: Query<
+    (Entity, &Health, &mut BossPhases),
+    Or<(Added<BossPhases>, Changed<Health>)>,
+  >,
+  mut entered: EventWriter<BossPhaseEntered>,
+  mut exited: EventWriter<BossPhaseExited>,
+) {
+  for (boss, health, mut phases) in &mut query {
+    let target = phases.phase_for(health.fraction());
+
+    let next = match phases.current {
+      None => {
+        entered.send(BossPhaseEntered { boss, phase: 0 });
+        0
+      }
+      Some(current) => current,
+    };
+
+    // Phases never go back, even if the boss gets healed.
+    for phase in next..target {
+      exited.send(BossPhaseExited { boss, phase });
+      entered.send(BossPhaseEntered {
+        boss,
+        phase: phase + 1,
+      });
+    }
+
+    phases.current = Some(next.max(target));
+  }
+}
+
+fn log_boss_phase(
+  mut entered: EventReader<BossPhaseEntered>,
+  mut exited: EventReader<BossPhaseExited>,
+  query: Query<(&Boss, &BossPhases)>,
+) {
+  for event in exited.read() {
+    let Ok((boss, phases)) = query.get(event.boss) else {
+      continue;
+    };
+
+    if let Some(phase) = phases.phases.get(event.phase) {
+      info!(
+        "{} left phase {}",
+        boss.name, phase.name
+      );
+    }
+  }
+
+  for event in entered.read() {
+    let Ok((boss, phases)) = query.get(event.boss) else {
+      continue;
+    };
+
+    if let Some(phase) = phases.phases.get(event.phase) {
+      info!(
+        "{} entered phase {}",
+        boss.name, phase.name
+      );
+    }
+  }
+}
+
+/// Screen shake trauma added when a boss enters a later phase.
+const PHASE_CHANGE_TRAUMA: f32 = 0.6;
+
+/// Bosses roar when they appear and whenever they change phase, shaking
+/// the screen from the second phase on.
+fn roar_on_new_phase(
+  mut entered: EventReader<BossPhaseEntered>,
+  mut sound_effects: EventWriter<SoundEffect>,
+  mut screen_shake: ResMut<ScreenShake>,
+) {
+  let mut roared = false;
+
+  for event in entered.read() {
+    roared = true;
+
+    if event.phase > 0 {
+      screen_shake.add_trauma(PHASE_CHANGE_TRAUMA);
+    }
+  }
+
+  if roared {
+    sound_effects.send(SoundEffect::BossRoar);
+  }
+}
@@ -0,0 +1,252 @@
+use bevy::utils::HashSet;
+
+use crate::{
+  audio::SoundEffect, difficulty::Difficulty, prelude::*, settings::Settings,
+};
+
+use super::particles::ParticleBurst;
+use super::screen_shake::ScreenShake;
+
+pub struct CombatPlugin;
+
+impl Plugin for CombatPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .add_event::<DamageEvent>()
+      .add_event::<HitEvent>()
+      .add_event::<DeathEvent>()
+      .register_type::<Health>()
+      .add_systems(
+        Update,
+        (
+          apply_damage.in_set(CombatSystems),
+          tick_invulnerability,
+        )
+          .chain()
+          .run_if(in_state(InGameState::Running)),
+      );
+  }
+}
+
+/// The system turning [`DamageEvent`]s into [`HitEvent`]s and
+/// [`DeathEvent`]s. Their readers run after it to see them the same frame.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CombatSystems;
+
+/// The side an entity fights for.
+/// Damage is never dealt between entities of the same faction.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Faction {
+  Player,
+  Boss,
+}
+
+/// Hit points of an entity that can be damaged and killed.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Health {
+  pub current: f32,
+  pub max: f32,
+}
+
+impl Health {
+  pub fn new(max: f32) -> Self {
+    Self { current: max, max }
+  }
+
+  /// Remaining health as a value between `0.0` and `1.0`.
+  pub fn fraction(&self) -> f32 {
+    if self.max <= 0.0 {
+      return 0.0;
+    }
+
+    (self.current / self.max).clamp(0.0, 1.0)
+  }
+
+  pub fn is_dead(&self) -> bool {
+    self.current <= 0.0
+  }
+}
+
+/// How long an entity stays invulnerable after taking a hit.
+/// Entities without this component can be hit every frame.
+#[derive(Component)]
+pub struct HitInvulnerability(pub f32);
+
+/// While present, every [`DamageEvent`] targeting the entity is ignored.
+/// The component removes itself when the timer finishes.
+#[derive(Component, Deref, DerefMut)]
+pub struct Invulnerable(pub Timer);
+
+impl Invulnerable {
+  pub fn from_seconds(duration: f32) -> Self {
+    Self(Timer::from_seconds(
+      duration,
+      TimerMode::Once,
+    ))
+  }
+}
+
+/// An event sent to deal damage to an entity with [`Health`].
+#[derive(Event)]
+pub struct DamageEvent {
+  pub target: Entity,
+  /// The faction dealing the damage, which can't hurt its own entities.
+  pub source: Faction,
+  pub amount: f32,
+}
+
+/// An event sent when a [`DamageEvent`] lowers the [`Health`] of an entity
+/// of a [`Faction`], with the health it lost.
+#[derive(Event)]
+pub struct HitEvent {
+  pub faction: Faction,
+  pub amount: f32,
+}
+
+/// An event sent when an entity's [`Health`] reaches zero.
+#[derive(Event)]
+pub struct DeathEvent {
+  pub entity: Entity,
+  pub faction: Faction,
+}
+
+/// Screen shake trauma added when the player gets hit.
+const PLAYER_HIT_TRAUMA: f32 = 0.5;
+const PLAYER_HIT_COLOR: Color = Color::srgb(1.0, 0.3, 0.3);
+const BOSS_HIT_COLOR: Color = Color::srgb(1.0, 0.85, 0.4);
+
+/// Subtracts [`DamageEvent`]s from [`Health`], grants invulnerability frames
+/// and sends a [`DeathEvent`] when an entity runs out of health.
+/// Damage to the player is scaled by the [`Difficulty`], damage to the
+/// source's own [`Faction`] is ignored.
+fn apply_damage(
+  mut commands: Commands,
+  difficulty: Res<Difficulty>,
+  mut damage_events: EventReader<DamageEvent>,
+  mut hit_events: EventWriter<HitEvent>,
+  mut death_events: EventWriter<DeathEvent>,
+  mut sound_effects: EventWriter<SoundEffect>,
+  mut particle_bursts: EventWriter<ParticleBurst>,
+  mut screen_shake: ResMut<ScreenShake>,
+  mut query: Query<(
+    &mut Health,
+    &Faction,
+    &GlobalTransform,
+    Has<Invulnerable>,
+    Option<&HitInvulnerability>,
+  )>,
+) {
+  // Invulnerability is inserted through commands, so keep track of the
+  // entities that already got hit during this run of the system.
+  let mut hit = HashSet::new();
+
+  for event in damage_events.read() {
+    let Ok((mut health, faction, transform, invulnerable, hit_invulnerability)) =
+      query.get_mut(event.target)
+    else {
+      continue;
+    };
+
+    if *faction == event.source
+      || invulnerable
+      || health.is_dead()
+      || hit.contains(&event.target)
+    {
+      continue;
+    }
+
+    let (amount, min_health, extra_invulnerability) = match faction {
+      Faction::Player => (
+        event.amount * difficulty.damage_taken(),
+        // With no-fail the last hit point is never taken.
+        match difficulty.assists.no_fail {
+          true => health.current.min(1.0),
+          false => 0.0,
+        },
+        difficulty.extra_invulnerability(),
+      ),
+      Faction::Boss => (event.amount, 0.0, 0.0),
+    };
+
+    let previous = health.current;
+    health.current = (health.current - amount).max(min_health);
+
+    // Hits clamped by no-fail or dealing no damage are ignored entirely.
+    if health.current >= previous {
+      continue;
+    }
+
+    hit_events.send(HitEvent {
+      faction: *faction,
+      amount: previous - health.current,
+    });
+    sound_effects.send(SoundEffect::Hit);
+    particle_bursts.send(ParticleBurst {
+      position: transform.translation().truncate(),
+      color: match faction {
+        Faction::Player => PLAYER_HIT_COLOR,
+        Faction::Boss => BOSS_HIT_COLOR,
+      },
+      count: 16,
+      speed: 160.0,
+      lifetime: 0.4,
+    });
+
+    if *faction == Faction::Player {
+      screen_shake.add_trauma(PLAYER_HIT_TRAUMA);
+    }
+
+    if health.is_dead() {
+      death_events.send(DeathEvent {
+        entity: event.target,
+        faction: *faction,
+      });
+      continue;
+    }
+
+    if let Some(hit_invulnerability) = hit_invulnerability {
+      hit.insert(event.target);
+      commands
+        .entity(event.target)
+        .insert(Invulnerable::from_seconds(
+          hit_invulnerability.0 + extra_invulnerability,
+        ));
+    }
+  }
+}
+
+const BLINK_INTERVAL: f32 = 0.1;
+const INVULNERABLE_ALPHA: f32 = 0.25;
+
+/// Ticks [`Invulnerable`] timers, blinking the sprite while they run.
+/// With reduced flashing the sprite stays faded instead.
+fn tick_invulnerability(
+  mut commands: Commands,
+  time: Res<Time>,
+  settings: Res<Settings>,
+  mut query: Query<(
+    Entity,
+    &mut Invulnerable,
+    Option<&mut Sprite>,
+  )>,
+) {
+  for (entity, mut invulnerable, sprite) in &mut query {
+    invulnerable.tick(time.delta());
+
+    let finished = invulnerable.finished();
+
+    if let Some(mut sprite) = sprite {
+      let visible = finished
+        || (!settings.reduced_flashing
+          && (invulnerable.elapsed_secs() / BLINK_INTERVAL) as u32 % 2 == 1);
+      sprite
+        .color
+        .set_alpha(if visible { 1.0 } else { INVULNERABLE_ALPHA });
+    }
+
+    if finished {
+      commands.entity(entity).remove::<Invulnerable>();
+    }
+  }
+}
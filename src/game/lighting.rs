@@ -0,0 +1,45 @@
+use bevy_light_2d::prelude::*;
+
+use crate::{
+  prelude::*,
+  settings::{Quality, Settings},
+};
+
+pub struct LightingPlugin;
+
+impl Plugin for LightingPlugin {
+  fn build(&self, app: &mut App) {
+    app.add_systems(Update, update_quality_lights);
+  }
+}
+
+/// A [`PointLight2d`] only lit from a [`Quality`] preset up.
+#[derive(Component)]
+pub struct QualityLight {
+  pub light: PointLight2d,
+  pub min_quality: Quality,
+}
+
+fn update_quality_lights(
+  mut commands: Commands,
+  settings: Res<Settings>,
+  lights: Query<(
+    Entity,
+    Ref<QualityLight>,
+    Has<PointLight2d>,
+  )>,
+) {
+  for (entity, light, lit) in &lights {
+    if !settings.is_changed() && !light.is_added() {
+      continue;
+    }
+
+    let enabled = settings.quality >= light.min_quality;
+
+    if enabled && !lit {
+      commands.entity(entity).insert(light.light.clone());
+    } else if !enabled && lit {
+      commands.entity(entity).remove::<PointLight2d>();
+    }
+  }
+}
@@ -1,21 +1,34 @@
+mod animation;
+mod boss;
+mod combat;
+mod lighting;
+mod music;
+mod particles;
 mod pause;
 mod player;
+mod projectile;
+mod rush;
+mod scoring;
+mod screen_shake;
+mod speedrun;
+mod telegraph;
 mod ui;
 
+pub use boss::BossDefinition;
+pub use scoring::{FightScore, ScoringRules};
+pub use speedrun::format_time;
+
 use avian2d::prelude::*;
 
 use bevy_light_2d::light::AmbientLight2d;
 
-use crate::{assets::ExampleAssets, prelude::*};
+use crate::prelude::*;
 
-#[derive(Component, Deref, DerefMut)]
-struct AnimationTimer(Timer);
+const ARENA_HALF_SIZE: Vec2 = Vec2::new(800., 600.);
 
-#[derive(Component)]
-struct AnimationIndices {
-  first: usize,
-  last: usize,
-}
+/// The area the fights take place in.
+#[derive(Resource)]
+pub struct ArenaBounds(pub Rect);
 
 pub struct GamePlugin<S: States> {
   pub state: S,
@@ -25,21 +38,28 @@ impl<S: States> Plugin for GamePlugin<S> {
   fn build(&self, app: &mut App) {
     app
       .add_plugins((
+        animation::AnimationPlugin,
+        lighting::LightingPlugin,
+        particles::ParticlesPlugin,
+        combat::CombatPlugin,
+        music::FightMusicPlugin,
         pause::PausePlugin,
         ui::UiPlugin,
         PhysicsPlugins::default().with_length_unit(20.),
         player::PlayerPlugin,
+        boss::BossPlugin,
+        projectile::ProjectilePlugin,
+        rush::RushPlugin,
+        screen_shake::ScreenShakePlugin,
+        speedrun::SpeedrunPlugin,
+        telegraph::TelegraphPlugin,
       ))
+      .add_plugins(scoring::ScoringPlugin)
       .insert_resource(Gravity(Vec2::new(0., 0.)))
-      .add_systems(
-        OnEnter(self.state.clone()),
-        (setup_game, spawn_example_tree),
-      )
-      .add_systems(
-        Update,
-        (animate_sprite.run_if(in_state(InGameState::Running)))
-          .run_if(in_state(AppState::InGame)),
-      );
+      .insert_resource(ArenaBounds(
+        Rect::from_center_half_size(Vec2::ZERO, ARENA_HALF_SIZE),
+      ))
+      .add_systems(OnEnter(self.state.clone()), setup_game);
   }
 }
 
@@ -56,59 +76,3 @@ fn setup_game(mut commands: Commands) {
     StateScoped(AppState::InGame),
   ));
 }
-
-fn spawn_example_tree(
-  mut commands: Commands,
-  example_assets: Res<ExampleAssets>,
-  mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
-) {
-  let layout =
-    TextureAtlasLayout::from_grid(UVec2::splat(64), 16, 1, None, None);
-
-  let texture_atlas_layout = texture_atlas_layouts.add(layout);
-
-  commands.spawn((
-    Name::new("Tree"),
-    StateScoped(AppState::InGame),
-    Transform::from_xyz(250., 0., 0.),
-    Sprite::from_atlas_image(
-      example_assets.tree.clone(),
-      TextureAtlas {
-        layout: texture_atlas_layout,
-        index: 0,
-      },
-    ),
-    RigidBody::Static,
-    Collider::rectangle(64.0, 64.0), // Add a collider (adjust size as needed)
-    Friction::ZERO.with_combine_rule(CoefficientCombine::Min), // Optional: configure physics properties
-    Restitution::ZERO.with_combine_rule(CoefficientCombine::Min),
-    AnimationIndices { first: 0, last: 15 },
-    AnimationTimer(Timer::from_seconds(
-      0.1,
-      TimerMode::Repeating,
-    )),
-  ));
-}
-
-fn animate_sprite(
-  time: Res<Time>,
-  mut query: Query<(
-    &AnimationIndices,
-    &mut AnimationTimer,
-    &mut Sprite,
-  )>,
-) {
-  for (indices, mut timer, mut sprite) in &mut query {
-    timer.tick(time.delta());
-
-    if timer.just_finished() {
-      if let Some(atlas) = &mut sprite.texture_atlas {
-        atlas.index = if atlas.index == indices.last {
-          indices.first
-        } else {
-          atlas.index + 1
-        };
-      }
-    }
-  }
-}
@@ -0,0 +1,77 @@
+use crate::{
+  audio::{MusicController, MusicLayer},
+  prelude::*,
+};
+
+use super::boss::BossPhaseEntered;
+use super::combat::Health;
+use super::player::Player;
+
+/// The danger layer plays while the player health fraction is at or below
+/// this value.
+const DANGER_HEALTH: f32 = 0.4;
+
+/// Drives the layers of the fight music from the state of the fight.
+pub struct FightMusicPlugin;
+
+impl Plugin for FightMusicPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .add_systems(
+        OnEnter(AppState::InGame),
+        reset_music_layers,
+      )
+      .add_systems(
+        OnEnter(FightState::Fighting),
+        start_fight_layers,
+      )
+      .add_systems(
+        OnEnter(FightState::Intermission),
+        stop_fight_layers,
+      )
+      .add_systems(
+        Update,
+        (phase_music_layers, danger_music_layer)
+          .run_if(in_state(InGameState::Running)),
+      );
+  }
+}
+
+fn reset_music_layers(mut controller: ResMut<MusicController>) {
+  controller.reset();
+}
+
+fn start_fight_layers(mut controller: ResMut<MusicController>) {
+  controller.fade_in(MusicLayer::Drums);
+}
+
+/// Only the base of the song keeps playing between bosses.
+fn stop_fight_layers(mut controller: ResMut<MusicController>) {
+  controller.fade_out(MusicLayer::Drums);
+  controller.fade_out(MusicLayer::Lead);
+}
+
+/// The lead joins once a boss leaves its first phase.
+fn phase_music_layers(
+  mut entered: EventReader<BossPhaseEntered>,
+  mut controller: ResMut<MusicController>,
+) {
+  if entered.read().any(|event| event.phase > 0) {
+    controller.fade_in(MusicLayer::Lead);
+  }
+}
+
+fn danger_music_layer(
+  player: Query<&Health, (With<Player>, Changed<Health>)>,
+  mut controller: ResMut<MusicController>,
+) {
+  let Ok(health) = player.get_single() else {
+    return;
+  };
+
+  if !health.is_dead() && health.fraction() <= DANGER_HEALTH {
+    controller.fade_in(MusicLayer::Danger);
+  } else {
+    controller.fade_out(MusicLayer::Danger);
+  }
+}
@@ -0,0 +1,90 @@
+use std::f32::consts::TAU;
+
+use crate::{prelude::*, settings::Settings};
+
+const PARTICLE_SIZE: f32 = 4.;
+
+pub struct ParticlesPlugin;
+
+impl Plugin for ParticlesPlugin {
+  fn build(&self, app: &mut App) {
+    app.add_event::<ParticleBurst>().add_systems(
+      Update,
+      (spawn_particle_bursts, update_particles)
+        .chain()
+        .run_if(in_state(InGameState::Running)),
+    );
+  }
+}
+
+/// An event sent to scatter particles around a point.
+/// Fewer particles are spawned on lower quality presets.
+#[derive(Event)]
+pub struct ParticleBurst {
+  pub position: Vec2,
+  pub color: Color,
+  pub count: usize,
+  pub speed: f32,
+  pub lifetime: f32,
+}
+
+#[derive(Component)]
+struct Particle {
+  velocity: Vec2,
+  lifetime: Timer,
+}
+
+fn spawn_particle_bursts(
+  mut commands: Commands,
+  settings: Res<Settings>,
+  mut bursts: EventReader<ParticleBurst>,
+) {
+  let density = settings.quality.particle_density();
+
+  for burst in bursts.read() {
+    let count = (burst.count as f32 * density).round() as usize;
+
+    for index in 0..count {
+      // Evenly spread, with the speed varied so the burst isn't a ring.
+      let angle = index as f32 * TAU / count as f32;
+      let speed = burst.speed * (0.5 + 0.5 * ((index * 7 % 5) as f32 / 4.));
+
+      commands.spawn((
+        Name::new("Particle"),
+        Particle {
+          velocity: Vec2::from_angle(angle) * speed,
+          lifetime: Timer::from_seconds(burst.lifetime, TimerMode::Once),
+        },
+        StateScoped(AppState::InGame),
+        Sprite::from_color(burst.color, Vec2::splat(PARTICLE_SIZE)),
+        Transform::from_translation(burst.position.extend(2.0)),
+      ));
+    }
+  }
+}
+
+/// Moves particles and fades them out over their lifetime.
+fn update_particles(
+  mut commands: Commands,
+  time: Res<Time>,
+  mut particles: Query<(
+    Entity,
+    &mut Particle,
+    &mut Transform,
+    &mut Sprite,
+  )>,
+) {
+  for (entity, mut particle, mut transform, mut sprite) in &mut particles {
+    particle.lifetime.tick(time.delta());
+
+    if particle.lifetime.finished() {
+      commands.entity(entity).despawn();
+      continue;
+    }
+
+    transform.translation += (particle.velocity * time.delta_secs()).extend(0.);
+    sprite
+      .color
+      .set_alpha(particle.lifetime.fraction_remaining());
+  }
+}
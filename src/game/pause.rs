@@ -1,26 +1,24 @@
 use assets::UiAssets;
 
-use crate::prelude::*;
+use crate::{navigation::BackButton, prelude::*};
 
 pub struct PausePlugin;
 
-#[derive(Component)]
-struct MainMenuButton;
-
 impl Plugin for PausePlugin {
   fn build(&self, app: &mut App) {
     app
       .add_systems(
         OnEnter(InGameState::Paused),
-        setup_paused_screen,
+        (setup_paused_screen, pause_time),
       )
+      // Also runs when leaving the run from the pause menu.
       .add_systems(
-        Update,
-        toggle_pause.run_if(in_state(AppState::InGame)),
+        OnExit(InGameState::Paused),
+        unpause_time,
       )
       .add_systems(
         Update,
-        go_to_main_menu.run_if(in_state(InGameState::Paused)),
+        toggle_pause.run_if(in_state(AppState::InGame)),
       );
   }
 }
@@ -47,7 +45,7 @@ pub fn setup_paused_screen(mut commands: Commands, ui: Res<UiAssets>) {
 
   let title = commands
     .spawn((
-      Text::new("Paused"),
+      LocalizedText::new("pause.title"),
       TextFont {
         font_size: 33.0,
         ..default()
@@ -56,11 +54,31 @@ pub fn setup_paused_screen(mut commands: Commands, ui: Res<UiAssets>) {
     ))
     .id();
 
+  let resume_button = commands
+    .spawn((
+      Button,
+      BackButton,
+      ButtonAction::run_system(resume),
+    ))
+    .with_children(|parent| {
+      parent.spawn((
+        LocalizedText::new("pause.resume"),
+        TextFont {
+          font: ui.font.clone(),
+          ..Default::default()
+        },
+      ));
+    })
+    .id();
+
   let main_menu_button = commands
-    .spawn((Button, MainMenuButton))
+    .spawn((
+      Button,
+      ButtonAction::set_state(AppState::MainMenu),
+    ))
     .with_children(|parent| {
       parent.spawn((
-        Text::new("Return to Main Menu"),
+        LocalizedText::new("common.main_menu"),
         TextFont {
           font: ui.font.clone(),
           ..Default::default()
@@ -69,46 +87,36 @@ pub fn setup_paused_screen(mut commands: Commands, ui: Res<UiAssets>) {
     })
     .id();
 
-  commands
-    .entity(container)
-    .add_children(&[title, main_menu_button]);
+  commands.entity(container).add_children(&[
+    title,
+    resume_button,
+    main_menu_button,
+  ]);
 }
 
 fn toggle_pause(
-  input: Res<ButtonInput<KeyCode>>,
+  actions: Res<ActionState>,
   current_state: Res<State<InGameState>>,
   mut next_state: ResMut<NextState<InGameState>>,
-  mut time: ResMut<Time<Virtual>>,
 ) {
-  if input.just_pressed(KeyCode::Escape) {
+  if actions.just_pressed(Action::Pause) {
     let state = match current_state.get() {
       InGameState::Running => InGameState::Paused,
       InGameState::Paused => InGameState::Running,
     };
 
     next_state.set(state);
-
-    if state.eq(&InGameState::Paused) {
-      time.pause();
-    } else {
-      time.unpause();
-    };
   }
 }
 
-fn go_to_main_menu(
-  query: Query<
-    &Interaction,
-    (
-      With<MainMenuButton>,
-      Changed<Interaction>,
-    ),
-  >,
-  mut next_state: ResMut<NextState<AppState>>,
-) {
-  for interaction in &query {
-    if interaction == &Interaction::Pressed {
-      next_state.set(AppState::MainMenu);
-    }
-  }
+fn resume(mut next_state: ResMut<NextState<InGameState>>) {
+  next_state.set(InGameState::Running);
+}
+
+fn pause_time(mut time: ResMut<Time<Virtual>>) {
+  time.pause();
+}
+
+fn unpause_time(mut time: ResMut<Time<Virtual>>) {
+  time.unpause();
 }
@@ -0,0 +1,120 @@
+use avian2d::prelude::*;
+
+use crate::prelude::*;
+
+use super::{attack::MeleeAttack, Dashing, Facing, Player};
+use crate::game::animation::{AnimationClip, SpriteAnimation};
+use crate::game::combat::Health;
+
+/// Speed above which the player is considered walking.
+const WALK_THRESHOLD: f32 = 20.0;
+
+/// What the player is doing, as far as its sprite is concerned.
+///
+/// Variants are ordered by priority: a state interrupts every state
+/// before it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PlayerAnimationState {
+  #[default]
+  Idle,
+  Walk,
+  Attack,
+  Dash,
+  Hurt,
+  Die,
+}
+
+impl PlayerAnimationState {
+  /// The clip of the state for a facing direction.
+  ///
+  /// The placeholder sheet has 3 rows of walking frames, each with 2 outfits
+  /// of 4 facings, so the other states reuse those frames.
+  fn clip(self, facing: Facing) -> AnimationClip {
+    let offset = match facing {
+      Facing::Left => 0,
+      Facing::Down => 1,
+      Facing::Up => 2,
+      Facing::Right => 3,
+    };
+    let [first, second, third] = [4, 12, 20].map(|row| row + offset);
+    let other_outfit = 16 + offset;
+
+    match self {
+      Self::Idle => AnimationClip::new(vec![third], 1.0),
+      Self::Walk => {
+        AnimationClip::new(vec![first, second, third, second], 0.15)
+      }
+      Self::Attack => AnimationClip::new(vec![second, first], 0.08).once(),
+      Self::Dash => AnimationClip::new(vec![first], 1.0),
+      Self::Hurt => AnimationClip::new(
+        vec![other_outfit, third, other_outfit, third],
+        0.05,
+      )
+      .once(),
+      Self::Die => AnimationClip::new(vec![other_outfit], 1.0).once(),
+    }
+  }
+
+  /// Whether the state plays its whole clip before a lower priority state
+  /// can take over.
+  fn plays_to_end(self) -> bool {
+    matches!(
+      self,
+      Self::Attack | Self::Hurt | Self::Die
+    )
+  }
+}
+
+/// Picks the animation of the player from its movement and combat state.
+#[derive(Component, Default)]
+pub struct PlayerAnimator {
+  state: PlayerAnimationState,
+  /// Health on the previous frame, to notice hits.
+  last_health: Option<f32>,
+}
+
+pub(super) fn update_player_animation(
+  mut player: Query<
+    (
+      &mut PlayerAnimator,
+      &mut SpriteAnimation,
+      &Facing,
+      &LinearVelocity,
+      &Health,
+      &MeleeAttack,
+      Has<Dashing>,
+    ),
+    With<Player>,
+  >,
+) {
+  for (mut animator, mut animation, facing, velocity, health, melee, dashing) in
+    &mut player
+  {
+    let hurt = animator
+      .last_health
+      .is_some_and(|last_health| health.current < last_health);
+    animator.last_health = Some(health.current);
+
+    let next = if health.is_dead() {
+      PlayerAnimationState::Die
+    } else if hurt {
+      PlayerAnimationState::Hurt
+    } else if dashing {
+      PlayerAnimationState::Dash
+    } else if melee.is_attacking() {
+      PlayerAnimationState::Attack
+    } else if velocity.length() > WALK_THRESHOLD {
+      PlayerAnimationState::Walk
+    } else {
+      PlayerAnimationState::Idle
+    };
+
+    let current = animator.state;
+    if next < current && current.plays_to_end() && !animation.is_finished() {
+      continue;
+    }
+
+    animator.state = next;
+    animation.play(next.clip(*facing));
+  }
+}
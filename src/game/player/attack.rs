@@ -0,0 +1,149 @@
+use avian2d::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::prelude::*;
+
+use super::{Facing, Player};
+use crate::game::combat::{DamageEvent, Faction};
+
+const MELEE_DAMAGE: f32 = 3.0;
+/// Seconds between two melee attacks.
+const MELEE_COOLDOWN: f32 = 0.4;
+/// Seconds the hitbox of a melee attack stays active.
+const MELEE_DURATION: f32 = 0.12;
+/// Distance between the player and the center of the hitbox.
+const MELEE_REACH: f32 = 30.0;
+/// Size of the hitbox, with `x` along the facing direction.
+const MELEE_SIZE: Vec2 = Vec2::new(28.0, 40.0);
+
+/// An event sent for an attack input action.
+#[derive(Event)]
+pub struct AttackAction;
+
+/// The melee attack of the player, with its cooldown.
+#[derive(Component)]
+pub struct MeleeAttack {
+  cooldown: Timer,
+}
+
+impl Default for MeleeAttack {
+  fn default() -> Self {
+    let mut cooldown = Timer::from_seconds(MELEE_COOLDOWN, TimerMode::Once);
+    cooldown.set_elapsed(cooldown.duration());
+
+    Self { cooldown }
+  }
+}
+
+impl MeleeAttack {
+  /// Whether an attack started less than [`MELEE_DURATION`] seconds ago.
+  pub fn is_attacking(&self) -> bool {
+    self.cooldown.elapsed_secs() < MELEE_DURATION
+  }
+
+  /// How far the cooldown is, `1.0` once the attack is ready.
+  pub fn readiness(&self) -> f32 {
+    self.cooldown.fraction()
+  }
+}
+
+/// A short-lived sensor that damages every entity of another [`Faction`]
+/// it touches, once.
+#[derive(Component)]
+pub struct Hitbox {
+  pub damage: f32,
+  pub faction: Faction,
+  lifetime: Timer,
+  hit: HashSet<Entity>,
+}
+
+/// Sends [`AttackAction`] events for the attack action.
+pub(super) fn attack_input(
+  mut attack_event_writer: EventWriter<AttackAction>,
+  actions: Res<ActionState>,
+) {
+  if actions.just_pressed(Action::Attack) {
+    attack_event_writer.send(AttackAction);
+  }
+}
+
+/// Spawns a hitbox in front of the player when the attack is ready.
+pub(super) fn melee_attack(
+  mut commands: Commands,
+  time: Res<Time>,
+  mut attack_event_reader: EventReader<AttackAction>,
+  mut player: Query<(&Transform, &Facing, &mut MeleeAttack), With<Player>>,
+  mut hitboxes: Query<(Entity, &mut Hitbox)>,
+) {
+  let attacked = attack_event_reader.read().count() > 0;
+
+  for (transform, facing, mut melee) in &mut player {
+    melee.cooldown.tick(time.delta());
+
+    if !attacked || !melee.cooldown.finished() {
+      continue;
+    }
+
+    melee.cooldown.reset();
+
+    let direction = facing.direction();
+    let position = transform.translation.truncate() + direction * MELEE_REACH;
+
+    commands.spawn((
+      Name::new("MeleeHitbox"),
+      Hitbox {
+        damage: MELEE_DAMAGE,
+        faction: Faction::Player,
+        lifetime: Timer::from_seconds(MELEE_DURATION, TimerMode::Once),
+        hit: HashSet::new(),
+      },
+      StateScoped(AppState::InGame),
+      Transform::from_translation(position.extend(1.0)).with_rotation(
+        Quat::from_rotation_z(direction.to_angle()),
+      ),
+      Sprite::from_color(
+        Color::srgba(1.0, 1.0, 1.0, 0.4),
+        MELEE_SIZE,
+      ),
+      RigidBody::Kinematic,
+      Collider::rectangle(MELEE_SIZE.x, MELEE_SIZE.y),
+      Sensor,
+    ));
+  }
+
+  for (entity, mut hitbox) in &mut hitboxes {
+    if hitbox.lifetime.tick(time.delta()).finished() {
+      commands.entity(entity).despawn();
+    }
+  }
+}
+
+/// Damages the entities touched by hitboxes of another faction.
+pub(super) fn melee_hits(
+  mut collisions: EventReader<CollisionStarted>,
+  mut hitboxes: Query<&mut Hitbox>,
+  targets: Query<&Faction>,
+  mut damage_events: EventWriter<DamageEvent>,
+) {
+  for CollisionStarted(first, second) in collisions.read() {
+    for (hitbox_entity, target) in [(*first, *second), (*second, *first)] {
+      let Ok(mut hitbox) = hitboxes.get_mut(hitbox_entity) else {
+        continue;
+      };
+
+      let Ok(faction) = targets.get(target) else {
+        continue;
+      };
+
+      if *faction == hitbox.faction || !hitbox.hit.insert(target) {
+        continue;
+      }
+
+      damage_events.send(DamageEvent {
+        target,
+        source: hitbox.faction,
+        amount: hitbox.damage,
+      });
+    }
+  }
+}
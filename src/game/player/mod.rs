@@ -1,9 +1,25 @@
+mod animation;
+mod attack;
+
 use avian2d::{math::*, prelude::*};
 use bevy::prelude::*;
-
-use crate::{assets::ExampleAssets, prelude::*};
-
+use bevy_light_2d::prelude::PointLight2d;
+
+use crate::{
+  assets::ExampleAssets, audio::SoundEffect, difficulty::Difficulty,
+  prelude::*, settings::Quality,
+};
+
+use super::animation::SpriteAnimation;
+use super::combat::{
+  DeathEvent, Faction, Health, HitInvulnerability, Invulnerable,
+};
+use super::lighting::QualityLight;
+use super::particles::ParticleBurst;
 use super::InGameState;
+use animation::{update_player_animation, PlayerAnimator};
+pub use attack::MeleeAttack;
+use attack::{attack_input, melee_attack, melee_hits, AttackAction};
 
 pub struct PlayerPlugin;
 
@@ -11,12 +27,14 @@ impl Plugin for PlayerPlugin {
   fn build(&self, app: &mut App) {
     app
       .add_event::<MovementAction>()
+      .add_event::<AttackAction>()
       .add_systems(OnEnter(AppState::InGame), spawn_player)
       .add_systems(
         FixedUpdate,
         (
-          keyboard_input,
+          movement_input,
           movement,
+          update_dash,
           apply_movement_damping,
         )
           .run_if(in_state(InGameState::Running))
@@ -24,14 +42,37 @@ impl Plugin for PlayerPlugin {
       )
       .add_systems(
         Update,
-        update_camera.run_if(in_state(InGameState::Running)),
+        (
+          update_camera,
+          on_player_death,
+          dash_input,
+        )
+          .run_if(in_state(InGameState::Running)),
+      )
+      .add_systems(
+        Update,
+        (
+          attack_input,
+          melee_attack,
+          melee_hits,
+          update_player_animation,
+        )
+          .chain()
+          .run_if(in_state(InGameState::Running)),
       );
   }
 }
 
 // PLAYER SYSTEMS
 #[derive(Component)]
-struct Player;
+pub struct Player;
+
+/// Hit points the player starts every run with.
+const PLAYER_HEALTH: f32 = 5.0;
+/// Seconds of invulnerability granted to the player after being hit.
+const PLAYER_HIT_INVULNERABILITY: f32 = 1.0;
+const PLAYER_LIGHT_COLOR: Color = Color::srgb(1.0, 0.9, 0.7);
+const DASH_PARTICLE_COLOR: Color = Color::srgb(0.8, 0.9, 1.0);
 
 /// Spawn the player sprite and a 2D camera.
 fn spawn_player(
@@ -45,24 +86,53 @@ fn spawn_player(
 
   let texture_atlas_layout = texture_atlas_layouts.add(layout);
 
-  commands.spawn((
-    Name::new("Player"),
-    Player,
-    Mesh2d(meshes.add(Capsule2d::new(12.5, 20.0))),
-    Sprite::from_atlas_image(
-      example_assets.player.clone(),
-      TextureAtlas {
-        layout: texture_atlas_layout,
-        index: 21,
+  commands
+    .spawn((
+      Name::new("Player"),
+      Player,
+      Mesh2d(meshes.add(Capsule2d::new(12.5, 20.0))),
+      Sprite::from_atlas_image(
+        example_assets.player.clone(),
+        TextureAtlas {
+          layout: texture_atlas_layout,
+          index: 21,
+        },
+      ),
+      SpriteAnimation::default(),
+      PlayerAnimator::default(),
+      CharacterControllerBundle::new(Collider::capsule(12.5, 20.0))
+        .with_movement(1250.0, 0.92)
+        .with_dash(700.0, 0.18, 0.6),
+      Friction::ZERO.with_combine_rule(CoefficientCombine::Min),
+      Restitution::ZERO.with_combine_rule(CoefficientCombine::Min),
+      Transform::from_scale(Vec3::splat(1.)),
+      Faction::Player,
+      Health::new(PLAYER_HEALTH),
+      HitInvulnerability(PLAYER_HIT_INVULNERABILITY),
+      MeleeAttack::default(),
+      StateScoped(AppState::InGame),
+    ))
+    .with_child(QualityLight {
+      light: PointLight2d {
+        color: PLAYER_LIGHT_COLOR,
+        intensity: 1.0,
+        radius: 160.0,
+        ..Default::default()
       },
-    ),
-    CharacterControllerBundle::new(Collider::capsule(12.5, 20.0))
-      .with_movement(1250.0, 0.92),
-    Friction::ZERO.with_combine_rule(CoefficientCombine::Min),
-    Restitution::ZERO.with_combine_rule(CoefficientCombine::Min),
-    Transform::from_scale(Vec3::splat(1.)),
-    StateScoped(AppState::InGame),
-  ));
+      min_quality: Quality::Medium,
+    });
+}
+
+/// Ends the run when the player dies.
+fn on_player_death(
+  mut death_events: EventReader<DeathEvent>,
+  mut next_state: ResMut<NextState<AppState>>,
+) {
+  for event in death_events.read() {
+    if event.faction == Faction::Player {
+      next_state.set(AppState::GameOver);
+    }
+  }
 }
 
 // CAMERA SYSTEMS
@@ -98,6 +168,49 @@ fn update_camera(
 #[derive(Event)]
 pub enum MovementAction {
   Move(Vec2),
+  /// Dash towards a direction, or where the controller is facing if zero.
+  Dash(Vec2),
+}
+
+/// The direction a character controller is looking at.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Facing {
+  Left,
+  #[default]
+  Down,
+  Up,
+  Right,
+}
+
+impl Facing {
+  /// Picks the facing of a movement direction from its longest axis,
+  /// favouring the vertical one on diagonals.
+  fn from_direction(direction: Vec2) -> Option<Self> {
+    if direction == Vec2::ZERO {
+      None
+    } else if direction.y.abs() >= direction.x.abs() {
+      Some(if direction.y > 0.0 {
+        Self::Up
+      } else {
+        Self::Down
+      })
+    } else {
+      Some(if direction.x > 0.0 {
+        Self::Right
+      } else {
+        Self::Left
+      })
+    }
+  }
+
+  pub fn direction(self) -> Vec2 {
+    match self {
+      Self::Left => Vec2::NEG_X,
+      Self::Down => Vec2::NEG_Y,
+      Self::Up => Vec2::Y,
+      Self::Right => Vec2::X,
+    }
+  }
 }
 
 /// A marker component indicating that an entity is using a character controller.
@@ -113,16 +226,59 @@ pub struct MovementAcceleration(Scalar);
 #[derive(Component)]
 pub struct MovementDampingFactor(Scalar);
 
+/// The dash of a character controller: a burst of velocity with
+/// invulnerability frames, followed by a cooldown.
+#[derive(Component)]
+pub struct Dash {
+  speed: Scalar,
+  duration: f32,
+  cooldown: Timer,
+}
+
+impl Dash {
+  pub fn new(speed: Scalar, duration: f32, cooldown: f32) -> Self {
+    let mut cooldown = Timer::from_seconds(cooldown, TimerMode::Once);
+    cooldown.set_elapsed(cooldown.duration());
+
+    Self {
+      speed,
+      duration,
+      cooldown,
+    }
+  }
+
+  /// How far the cooldown is, `1.0` once the dash is ready.
+  pub fn readiness(&self) -> f32 {
+    self.cooldown.fraction()
+  }
+}
+
+impl Default for Dash {
+  fn default() -> Self {
+    Self::new(500.0, 0.15, 0.5)
+  }
+}
+
+/// Present while a character controller is dashing.
+/// Movement input and [`MovementDampingFactor`] are ignored meanwhile.
+#[derive(Component)]
+pub struct Dashing {
+  velocity: Vector,
+  timer: Timer,
+}
+
 /// A bundle that contains the components needed for a basic
 /// kinematic character controller.
 #[derive(Bundle)]
 pub struct CharacterControllerBundle {
   character_controller: CharacterController,
+  facing: Facing,
   rigid_body: RigidBody,
   collider: Collider,
   ground_caster: ShapeCaster,
   locked_axes: LockedAxes,
   movement: MovementBundle,
+  dash: Dash,
 }
 
 /// A bundle that contains components for character movement.
@@ -155,6 +311,7 @@ impl CharacterControllerBundle {
 
     Self {
       character_controller: CharacterController,
+      facing: Facing::default(),
       rigid_body: RigidBody::Dynamic,
       collider,
       ground_caster: ShapeCaster::new(
@@ -166,6 +323,7 @@ impl CharacterControllerBundle {
       .with_max_distance(10.0),
       locked_axes: LockedAxes::ROTATION_LOCKED,
       movement: MovementBundle::default(),
+      dash: Dash::default(),
     }
   }
 
@@ -177,23 +335,24 @@ impl CharacterControllerBundle {
     self.movement = MovementBundle::new(acceleration, damping);
     self
   }
+
+  pub fn with_dash(
+    mut self,
+    speed: Scalar,
+    duration: f32,
+    cooldown: f32,
+  ) -> Self {
+    self.dash = Dash::new(speed, duration, cooldown);
+    self
+  }
 }
 
-/// Sends [`MovementAction`] events based on keyboard input.
-fn keyboard_input(
+/// Sends [`MovementAction`] events based on the move actions.
+fn movement_input(
   mut movement_event_writer: EventWriter<MovementAction>,
-  keyboard_input: Res<ButtonInput<KeyCode>>,
+  actions: Res<ActionState>,
 ) {
-  let left = keyboard_input.any_pressed([KeyCode::KeyA, KeyCode::ArrowLeft]);
-  let right = keyboard_input.any_pressed([KeyCode::KeyD, KeyCode::ArrowRight]);
-  let up = keyboard_input.any_pressed([KeyCode::KeyW, KeyCode::ArrowUp]);
-  let down = keyboard_input.any_pressed([KeyCode::KeyS, KeyCode::ArrowDown]);
-
-  let horizontal = right as i8 - left as i8;
-  let vertical = up as i8 - down as i8;
-
-  // Create a direction vector for x and y axes
-  let direction = Vec2::new(horizontal as f32, vertical as f32);
+  let direction = actions.move_direction();
 
   // Send movement event only if there's input
   if direction.length_squared() > 0.0 {
@@ -201,14 +360,37 @@ fn keyboard_input(
   }
 }
 
+/// Sends [`MovementAction::Dash`] events for the dash action.
+/// Presses are read every frame, so they are not missed between two
+/// fixed updates.
+fn dash_input(
+  mut movement_event_writer: EventWriter<MovementAction>,
+  actions: Res<ActionState>,
+) {
+  if actions.just_pressed(Action::Dash) {
+    movement_event_writer.send(MovementAction::Dash(
+      actions.move_direction(),
+    ));
+  }
+}
+
 /// Responds to [`MovementAction`] events and moves character controllers accordingly.
 fn movement(
+  mut commands: Commands,
   time: Res<Time>,
+  difficulty: Res<Difficulty>,
   mut movement_event_reader: EventReader<MovementAction>,
+  mut sound_effects: EventWriter<SoundEffect>,
+  mut particle_bursts: EventWriter<ParticleBurst>,
   mut controllers: Query<(
+    Entity,
+    &GlobalTransform,
     &MovementAcceleration,
     &mut LinearVelocity,
-    &mut Sprite,
+    &mut Facing,
+    &Dash,
+    Has<Dashing>,
+    Option<&Invulnerable>,
   )>,
 ) {
   // Precision is adjusted so that the example works with
@@ -216,28 +398,65 @@ fn movement(
   let delta_time = time.delta_secs_f64().adjust_precision();
 
   for event in movement_event_reader.read() {
-    for (movement_acceleration, mut linear_velocity, mut sprite) in
-      &mut controllers
+    for (
+      entity,
+      transform,
+      movement_acceleration,
+      mut linear_velocity,
+      mut facing,
+      dash,
+      dashing,
+      invulnerable,
+    ) in &mut controllers
     {
       match event {
+        MovementAction::Move(_) if dashing => {}
         MovementAction::Move(direction) => {
-          let normalized = direction.normalize_or_zero();
+          // Keeps the magnitude of analog input, so sticks can walk.
+          let direction = direction.clamp_length_max(1.0);
 
           linear_velocity.x +=
-            normalized.x * movement_acceleration.0 * delta_time;
+            direction.x * movement_acceleration.0 * delta_time;
           linear_velocity.y +=
-            normalized.y * movement_acceleration.0 * delta_time;
-
-          if let Some(atlas) = &mut sprite.texture_atlas {
-            if direction.y > 0.0 {
-              atlas.index = 22;
-            } else if direction.y < 0.0 {
-              atlas.index = 21;
-            } else if direction.x > 0.0 {
-              atlas.index = 23;
-            } else if direction.x < 0.0 {
-              atlas.index = 20;
-            }
+            direction.y * movement_acceleration.0 * delta_time;
+
+          if let Some(new_facing) = Facing::from_direction(direction) {
+            *facing = new_facing;
+          }
+        }
+        MovementAction::Dash(_) if dashing => {}
+        MovementAction::Dash(_)
+          if !dash.cooldown.finished() && !difficulty.assists.infinite_dash => {
+        }
+        MovementAction::Dash(direction) => {
+          let direction = match direction.try_normalize() {
+            Some(direction) => direction,
+            None => facing.direction(),
+          };
+
+          linear_velocity.0 = direction * dash.speed;
+          sound_effects.send(SoundEffect::Dash);
+          particle_bursts.send(ParticleBurst {
+            position: transform.translation().truncate(),
+            color: DASH_PARTICLE_COLOR,
+            count: 12,
+            speed: 60.0,
+            lifetime: 0.3,
+          });
+
+          let mut entity_commands = commands.entity(entity);
+          entity_commands.insert(Dashing {
+            velocity: linear_velocity.0,
+            timer: Timer::from_seconds(dash.duration, TimerMode::Once),
+          });
+
+          // Don't cut short longer invulnerability, e.g. after a hit.
+          if invulnerable
+            .is_none_or(|timer| timer.remaining_secs() < dash.duration)
+          {
+            entity_commands.insert(Invulnerable::from_seconds(
+              dash.duration,
+            ));
           }
         }
       }
@@ -245,12 +464,41 @@ fn movement(
   }
 }
 
-/// Slows down movement in both directions.
-fn apply_movement_damping(
-  mut query: Query<(
-    &MovementDampingFactor,
+/// Keeps dashing controllers at full speed and runs the dash cooldowns.
+fn update_dash(
+  mut commands: Commands,
+  time: Res<Time>,
+  mut controllers: Query<(
+    Entity,
+    &mut Dash,
+    Option<&mut Dashing>,
     &mut LinearVelocity,
   )>,
+) {
+  for (entity, mut dash, dashing, mut linear_velocity) in &mut controllers {
+    let Some(mut dashing) = dashing else {
+      dash.cooldown.tick(time.delta());
+      continue;
+    };
+
+    linear_velocity.0 = dashing.velocity;
+
+    if dashing.timer.tick(time.delta()).finished() {
+      dash.cooldown.reset();
+      commands.entity(entity).remove::<Dashing>();
+    }
+  }
+}
+
+/// Slows down movement in both directions.
+fn apply_movement_damping(
+  mut query: Query<
+    (
+      &MovementDampingFactor,
+      &mut LinearVelocity,
+    ),
+    Without<Dashing>,
+  >,
 ) {
   for (damping_factor, mut linear_velocity) in &mut query {
     linear_velocity.x *= damping_factor.0;
@@ -0,0 +1,355 @@
+use std::f32::consts::TAU;
+
+use avian2d::prelude::*;
+use bevy::utils::HashSet;
+use bevy_light_2d::prelude::PointLight2d;
+use serde::Deserialize;
+
+use crate::{
+  difficulty::Difficulty,
+  prelude::*,
+  settings::{Quality, Settings},
+};
+
+use super::combat::{DamageEvent, Faction};
+use super::lighting::QualityLight;
+use super::player::Player;
+use super::ArenaBounds;
+
+/// Radius of a projectile, used for both its collider and its mesh.
+const PROJECTILE_RADIUS: f32 = 5.0;
+/// Shortest time between two volleys, so a pattern built outside of a boss
+/// definition can't fire on every frame or break the timer.
+const MIN_INTERVAL: f32 = 0.01;
+
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .add_systems(Startup, setup_projectile_assets)
+      .add_systems(
+        Update,
+        update_projectile_palette.run_if(resource_changed::<Settings>),
+      )
+      .add_systems(
+        Update,
+        (
+          fire_emitters,
+          apply_wave_motion,
+          projectile_hits,
+          despawn_out_of_arena,
+        )
+          .chain()
+          .run_if(in_state(InGameState::Running)),
+      );
+  }
+}
+
+/// The shape of a single volley of a [`BulletPattern`].
+#[derive(Debug, Clone, Deserialize)]
+pub enum PatternKind {
+  /// Projectiles spread evenly on a full circle.
+  Radial,
+  /// A radial volley rotated by `rotation` degrees after every shot.
+  Spiral { rotation: f32 },
+  /// A fan of projectiles aimed at the player, `spread` degrees wide.
+  Aimed { spread: f32 },
+  /// A radial volley whose projectiles sway sideways along a sine wave.
+  Wave { amplitude: f32, frequency: f32 },
+}
+
+/// A parameterized projectile pattern, fired by a [`ProjectileEmitter`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct BulletPattern {
+  pub kind: PatternKind,
+  /// Projectiles fired in every volley. Boss definitions reject `0`.
+  pub count: u32,
+  /// Boss definitions reject speeds that aren't finite.
+  pub speed: f32,
+  /// Seconds between two volleys. Boss definitions reject values that
+  /// aren't positive.
+  pub interval: f32,
+  /// Volleys fired before the emitter stops.
+  #[serde(default = "default_volleys")]
+  pub volleys: u32,
+  /// Angular offset of the whole pattern, in degrees.
+  #[serde(default)]
+  pub offset: f32,
+  #[serde(default = "default_damage")]
+  pub damage: f32,
+}
+
+fn default_volleys() -> u32 {
+  1
+}
+
+fn default_damage() -> f32 {
+  1.0
+}
+
+impl BulletPattern {
+  /// Directions of the projectiles of a volley, with `aim` pointing at the
+  /// target of aimed patterns.
+  fn directions(&self, volley: u32, aim: Vec2) -> Vec<Vec2> {
+    let count = self.count;
+    if count == 0 {
+      return Vec::new();
+    }
+
+    let offset = self.offset.to_radians();
+    let step = TAU / count as f32;
+
+    let (start, step) = match self.kind {
+      PatternKind::Radial | PatternKind::Wave { .. } => (offset, step),
+      PatternKind::Spiral { rotation } => (
+        offset + rotation.to_radians() * volley as f32,
+        step,
+      ),
+      PatternKind::Aimed { .. } if count == 1 => (aim.to_angle() + offset, 0.0),
+      PatternKind::Aimed { spread } => {
+        let spread = spread.to_radians();
+        (
+          aim.to_angle() + offset - spread / 2.0,
+          spread / (count - 1) as f32,
+        )
+      }
+    };
+
+    (0..count)
+      .map(|index| Vec2::from_angle(start + step * index as f32))
+      .collect()
+  }
+}
+
+/// Fires a [`BulletPattern`] from the entity position, one volley every
+/// `interval` seconds. The component removes itself after the last volley.
+#[derive(Component)]
+pub struct ProjectileEmitter {
+  pub pattern: BulletPattern,
+  pub faction: Faction,
+  timer: Timer,
+  fired: u32,
+}
+
+impl ProjectileEmitter {
+  pub fn new(pattern: BulletPattern, faction: Faction) -> Self {
+    let interval = match pattern.interval.is_finite() {
+      true => pattern.interval.max(MIN_INTERVAL),
+      false => MIN_INTERVAL,
+    };
+    let mut timer = Timer::from_seconds(interval, TimerMode::Repeating);
+    // The first volley is fired right away.
+    timer.set_elapsed(timer.duration());
+
+    Self {
+      pattern,
+      faction,
+      timer,
+      fired: 0,
+    }
+  }
+}
+
+/// A projectile that damages the first entity of another [`Faction`] it
+/// touches.
+#[derive(Component)]
+pub struct Projectile {
+  pub damage: f32,
+  pub faction: Faction,
+}
+
+/// Sine motion applied on top of the velocity of wave projectiles.
+#[derive(Component)]
+struct WaveMotion {
+  direction: Vec2,
+  speed: f32,
+  amplitude: f32,
+  frequency: f32,
+  elapsed: f32,
+}
+
+#[derive(Resource)]
+struct ProjectileAssets {
+  mesh: Handle<Mesh>,
+  material: Handle<ColorMaterial>,
+}
+
+fn setup_projectile_assets(
+  mut commands: Commands,
+  settings: Res<Settings>,
+  mut meshes: ResMut<Assets<Mesh>>,
+  mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+  commands.insert_resource(ProjectileAssets {
+    mesh: meshes.add(Circle::new(PROJECTILE_RADIUS)),
+    material: materials.add(settings.palette.projectile()),
+  });
+}
+
+fn update_projectile_palette(
+  settings: Res<Settings>,
+  assets: Res<ProjectileAssets>,
+  mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+  if let Some(material) = materials.get_mut(&assets.material) {
+    material.color = settings.palette.projectile();
+  }
+}
+
+fn fire_emitters(
+  mut commands: Commands,
+  time: Res<Time>,
+  assets: Res<ProjectileAssets>,
+  settings: Res<Settings>,
+  difficulty: Res<Difficulty>,
+  mut emitters: Query<(
+    Entity,
+    &GlobalTransform,
+    &mut ProjectileEmitter,
+  )>,
+  player: Query<&GlobalTransform, With<Player>>,
+) {
+  let target = player.get_single().ok().map(|t| t.translation().truncate());
+
+  for (entity, transform, mut emitter) in &mut emitters {
+    emitter.timer.tick(time.delta());
+
+    let origin = transform.translation().truncate();
+    let aim = target
+      .map(|target| (target - origin).normalize_or(Vec2::X))
+      .unwrap_or(Vec2::X);
+
+    for _ in 0..emitter.timer.times_finished_this_tick() {
+      if emitter.fired >= emitter.pattern.volleys {
+        break;
+      }
+
+      let pattern = &emitter.pattern;
+      let speed = match emitter.faction {
+        Faction::Boss => pattern.speed * difficulty.projectile_speed(),
+        Faction::Player => pattern.speed,
+      };
+
+      for direction in pattern.directions(emitter.fired, aim) {
+        let mut projectile = commands.spawn((
+          Name::new("Projectile"),
+          Projectile {
+            damage: pattern.damage,
+            faction: emitter.faction,
+          },
+          StateScoped(AppState::InGame),
+          Transform::from_translation(origin.extend(1.0)),
+          Mesh2d(assets.mesh.clone()),
+          MeshMaterial2d(assets.material.clone()),
+          RigidBody::Kinematic,
+          Collider::circle(PROJECTILE_RADIUS),
+          Sensor,
+          LinearVelocity(direction * speed),
+          QualityLight {
+            light: PointLight2d {
+              color: settings.palette.projectile(),
+              intensity: 0.5,
+              radius: 40.0,
+              ..Default::default()
+            },
+            min_quality: Quality::High,
+          },
+        ));
+
+        if let PatternKind::Wave {
+          amplitude,
+          frequency,
+        } = pattern.kind
+        {
+          projectile.insert(WaveMotion {
+            direction,
+            speed,
+            amplitude,
+            frequency,
+            elapsed: 0.0,
+          });
+        }
+      }
+
+      emitter.fired += 1;
+    }
+
+    if emitter.fired >= emitter.pattern.volleys {
+      commands.entity(entity).remove::<ProjectileEmitter>();
+    }
+  }
+}
+
+/// Sways wave projectiles perpendicular to their direction.
+fn apply_wave_motion(
+  time: Res<Time>,
+  mut query: Query<(&mut WaveMotion, &mut LinearVelocity)>,
+) {
+  for (mut wave, mut velocity) in &mut query {
+    wave.elapsed += time.delta_secs();
+
+    let phase = TAU * wave.frequency * wave.elapsed;
+    // Derivative of `amplitude * sin(phase)`, so the sideways offset
+    // stays bounded by the amplitude.
+    let sideways = wave.amplitude * TAU * wave.frequency * phase.cos();
+
+    velocity.0 = wave.direction * wave.speed + wave.direction.perp() * sideways;
+  }
+}
+
+/// Damages the entities touched by projectiles of another faction.
+fn projectile_hits(
+  mut commands: Commands,
+  mut collisions: EventReader<CollisionStarted>,
+  projectiles: Query<&Projectile>,
+  targets: Query<&Faction>,
+  mut damage_events: EventWriter<DamageEvent>,
+) {
+  // A projectile is spent on its first hit, even if it touched more
+  // than one entity during the same physics step.
+  let mut spent = HashSet::new();
+
+  for CollisionStarted(first, second) in collisions.read() {
+    for (projectile_entity, target) in [(*first, *second), (*second, *first)] {
+      let Ok(projectile) = projectiles.get(projectile_entity) else {
+        continue;
+      };
+
+      if spent.contains(&projectile_entity) {
+        continue;
+      }
+
+      let Ok(faction) = targets.get(target) else {
+        continue;
+      };
+
+      if *faction == projectile.faction {
+        continue;
+      }
+
+      damage_events.send(DamageEvent {
+        target,
+        source: projectile.faction,
+        amount: projectile.damage,
+      });
+
+      spent.insert(projectile_entity);
+      commands.entity(projectile_entity).despawn();
+    }
+  }
+}
+
+fn despawn_out_of_arena(
+  mut commands: Commands,
+  arena: Res<ArenaBounds>,
+  projectiles: Query<(Entity, &Transform), With<Projectile>>,
+) {
+  let bounds = arena.0.inflate(PROJECTILE_RADIUS);
+
+  for (entity, transform) in &projectiles {
+    if !bounds.contains(transform.translation.truncate()) {
+      commands.entity(entity).despawn();
+    }
+  }
+}
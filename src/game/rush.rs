@@ -0,0 +1,238 @@
+use crate::{
+  assets::BossAssets, difficulty::Difficulty, prelude::*, results::RunResult,
+  settings::Settings,
+};
+
+use super::boss::{Boss, BossDefinition};
+use super::combat::{CombatSystems, DeathEvent};
+use super::projectile::Projectile;
+use super::telegraph::Telegraph;
+
+/// Seconds the boss name is shown before the fight starts.
+const INTRO_DURATION: f32 = 2.0;
+/// Seconds between the death of a boss and the intro of the next one.
+const INTERMISSION_DURATION: f32 = 3.0;
+
+pub struct RushPlugin;
+
+impl Plugin for RushPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .add_systems(OnEnter(AppState::InGame), start_rush)
+      .add_systems(OnEnter(FightState::Intro), start_intro)
+      .add_systems(
+        OnEnter(FightState::Intermission),
+        (start_intermission, clear_attacks),
+      )
+      .add_systems(
+        Update,
+        (advance_rush, tick_fight_countdown)
+          .chain()
+          .in_set(RushSystems)
+          .after(CombatSystems)
+          .run_if(in_state(InGameState::Running)),
+      );
+  }
+}
+
+/// The systems moving between fights. [`advance_rush`] despawns the boss
+/// that died, so systems looking it up run before them.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RushSystems;
+
+/// The ordered list of bosses of the current run.
+#[derive(Resource)]
+pub struct BossRush {
+  pub bosses: Vec<Handle<BossDefinition>>,
+  /// Index of the boss being fought.
+  pub current: usize,
+}
+
+impl BossRush {
+  pub fn current_boss(&self) -> Option<Handle<BossDefinition>> {
+    self.bosses.get(self.current).cloned()
+  }
+}
+
+/// Moves to the `next` [`FightState`] once the timer finishes.
+#[derive(Resource)]
+struct FightCountdown {
+  timer: Timer,
+  next: FightState,
+}
+
+impl FightCountdown {
+  fn new(duration: f32, next: FightState) -> Self {
+    Self {
+      timer: Timer::from_seconds(duration, TimerMode::Once),
+      next,
+    }
+  }
+}
+
+fn start_rush(
+  mut commands: Commands,
+  boss_assets: Res<BossAssets>,
+  difficulty: Res<Difficulty>,
+  settings: Res<Settings>,
+) {
+  commands.insert_resource(RunResult::new(
+    difficulty.clone(),
+    settings.game_speed,
+    boss_assets.bosses.len(),
+  ));
+  commands.insert_resource(BossRush {
+    bosses: boss_assets.bosses.clone(),
+    current: 0,
+  });
+}
+
+fn start_intro(
+  mut commands: Commands,
+  ui: Res<assets::UiAssets>,
+  rush: Res<BossRush>,
+  definitions: Res<Assets<BossDefinition>>,
+) {
+  commands.insert_resource(FightCountdown::new(
+    INTRO_DURATION,
+    FightState::Fighting,
+  ));
+
+  let name = rush
+    .current_boss()
+    .and_then(|handle| definitions.get(&handle))
+    .map(|definition| definition.name.clone())
+    .unwrap_or_default();
+
+  spawn_banner(
+    &mut commands,
+    &ui,
+    FightState::Intro,
+    LocalizedText::new("rush.boss").with_arg("boss", name),
+    LocalizedText::new("rush.get_ready"),
+  );
+}
+
+fn start_intermission(
+  mut commands: Commands,
+  ui: Res<assets::UiAssets>,
+  rush: Res<BossRush>,
+  definitions: Res<Assets<BossDefinition>>,
+) {
+  commands.insert_resource(FightCountdown::new(
+    INTERMISSION_DURATION,
+    FightState::Intro,
+  ));
+
+  let next = rush
+    .current_boss()
+    .and_then(|handle| definitions.get(&handle))
+    .map(|definition| {
+      LocalizedText::new("rush.next_boss").with_arg("boss", &definition.name)
+    })
+    .unwrap_or_else(|| LocalizedText::new(""));
+
+  spawn_banner(
+    &mut commands,
+    &ui,
+    FightState::Intermission,
+    LocalizedText::new("rush.boss_defeated"),
+    next,
+  );
+}
+
+fn spawn_banner(
+  commands: &mut Commands,
+  ui: &assets::UiAssets,
+  state: FightState,
+  title: LocalizedText,
+  subtitle: LocalizedText,
+) {
+  commands
+    .spawn((
+      Name::new("FightBanner"),
+      StateScoped(state),
+      Node {
+        width: Val::Percent(100.),
+        position_type: PositionType::Absolute,
+        top: Val::Percent(25.),
+        flex_direction: FlexDirection::Column,
+        align_items: AlignItems::Center,
+        row_gap: Val::Px(12.),
+        ..Default::default()
+      },
+    ))
+    .with_children(|parent| {
+      parent.spawn((
+        title,
+        TextFont {
+          font: ui.font.clone(),
+          font_size: 48.,
+          ..Default::default()
+        },
+      ));
+      parent.spawn((
+        subtitle,
+        TextFont {
+          font: ui.font.clone(),
+          font_size: 24.,
+          ..Default::default()
+        },
+      ));
+    });
+}
+
+/// Removes the projectiles and telegraphs left over from the previous fight.
+fn clear_attacks(
+  mut commands: Commands,
+  attacks: Query<Entity, Or<(With<Projectile>, With<Telegraph>)>>,
+) {
+  for entity in &attacks {
+    commands.entity(entity).despawn_recursive();
+  }
+}
+
+/// Moves to the next boss when the current one dies, or ends the run with
+/// a victory after the last one.
+fn advance_rush(
+  mut commands: Commands,
+  mut death_events: EventReader<DeathEvent>,
+  bosses: Query<(), With<Boss>>,
+  mut rush: ResMut<BossRush>,
+  mut result: ResMut<RunResult>,
+  mut next_fight_state: ResMut<NextState<FightState>>,
+  mut next_app_state: ResMut<NextState<AppState>>,
+) {
+  for event in death_events.read() {
+    if !bosses.contains(event.entity) {
+      continue;
+    }
+
+    commands.entity(event.entity).despawn_recursive();
+
+    rush.current += 1;
+    result.bosses_defeated = rush.current;
+
+    if rush.current < rush.bosses.len() {
+      next_fight_state.set(FightState::Intermission);
+    } else {
+      next_app_state.set(AppState::Victory);
+    }
+  }
+}
+
+fn tick_fight_countdown(
+  mut commands: Commands,
+  time: Res<Time>,
+  countdown: Option<ResMut<FightCountdown>>,
+  mut next_state: ResMut<NextState<FightState>>,
+) {
+  let Some(mut countdown) = countdown else {
+    return;
+  };
+
+  if countdown.timer.tick(time.delta()).just_finished() {
+    next_state.set(countdown.next);
+    commands.remove_resource::<FightCountdown>();
+  }
+}
@@ -0,0 +1,417 @@
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{assets::ScoringAssets, prelude::*, results::RunResult};
+
+use super::boss::Boss;
+use super::combat::{
+  CombatSystems, DamageEvent, DeathEvent, Faction, HitEvent,
+};
+use super::format_time;
+use super::player::{Dashing, Player};
+use super::rush::RushSystems;
+
+pub struct ScoringPlugin;
+
+impl Plugin for ScoringPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .init_asset::<ScoringRules>()
+      .init_asset_loader::<ScoringRulesLoader>()
+      .add_systems(
+        OnEnter(FightState::Fighting),
+        reset_fight_stats,
+      )
+      .add_systems(
+        OnEnter(FightState::Intermission),
+        spawn_fight_results,
+      )
+      .add_systems(
+        Update,
+        (
+          tick_clear_time,
+          count_hits,
+          count_perfect_dodges,
+          score_fight,
+        )
+          .chain()
+          .after(CombatSystems)
+          .before(RushSystems)
+          .run_if(in_state(FightState::Fighting))
+          .run_if(in_state(InGameState::Running)),
+      );
+  }
+}
+
+/// How fights are scored, from `assets/fight.scoring.ron`.
+#[derive(Asset, TypePath, Debug, Deserialize)]
+pub struct ScoringRules {
+  /// Points of a fight cleared instantly.
+  pub clear_points: f32,
+  /// Points taken from [`ScoringRules::clear_points`] per second of fight,
+  /// down to zero.
+  pub time_penalty: f32,
+  /// Points lost per hit point of damage taken.
+  pub damage_penalty: f32,
+  /// Points per hit landed on the boss.
+  pub hit_points: f32,
+  /// Points per attack dodged by dashing through it.
+  pub perfect_dodge_points: f32,
+  /// Points added when the fight is cleared without taking damage.
+  pub no_hit_bonus: f32,
+  /// Lowest score of every rank but [`Rank::C`], which is the fallback.
+  pub ranks: Vec<RankThreshold>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RankThreshold {
+  pub rank: Rank,
+  pub score: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Rank {
+  S,
+  A,
+  B,
+  C,
+}
+
+impl Rank {
+  pub fn label(self) -> &'static str {
+    match self {
+      Self::S => "S",
+      Self::A => "A",
+      Self::B => "B",
+      Self::C => "C",
+    }
+  }
+}
+
+impl ScoringRules {
+  fn score(&self, stats: &FightStats) -> u32 {
+    let time =
+      (self.clear_points - self.time_penalty * stats.clear_time).max(0.0);
+    let no_hit = match stats.damage_taken > 0.0 {
+      true => 0.0,
+      false => self.no_hit_bonus,
+    };
+
+    let score = time
+      + self.hit_points * stats.hits_landed as f32
+      + self.perfect_dodge_points * stats.perfect_dodges as f32
+      - self.damage_penalty * stats.damage_taken
+      + no_hit;
+
+    score.max(0.0).round() as u32
+  }
+
+  fn rank(&self, score: u32) -> Rank {
+    self
+      .ranks
+      .iter()
+      .filter(|threshold| score >= threshold.score)
+      .max_by_key(|threshold| threshold.score)
+      .map_or(Rank::C, |threshold| threshold.rank)
+  }
+}
+
+/// What happened during the current fight, so far.
+#[derive(Resource, Default)]
+struct FightStats {
+  /// Seconds since the boss started attacking.
+  clear_time: f32,
+  damage_taken: f32,
+  hits_landed: u32,
+  perfect_dodges: u32,
+}
+
+/// The score of a fight won.
+#[derive(Debug, Clone)]
+pub struct FightScore {
+  pub boss: String,
+  pub clear_time: f32,
+  pub damage_taken: f32,
+  pub hits_landed: u32,
+  pub perfect_dodges: u32,
+  pub no_hit: bool,
+  pub score: u32,
+  pub rank: Rank,
+}
+
+fn reset_fight_stats(mut commands: Commands) {
+  commands.insert_resource(FightStats::default());
+}
+
+fn tick_clear_time(time: Res<Time>, mut stats: ResMut<FightStats>) {
+  stats.clear_time += time.delta_secs();
+}
+
+fn count_hits(
+  mut hit_events: EventReader<HitEvent>,
+  mut stats: ResMut<FightStats>,
+) {
+  for event in hit_events.read() {
+    match event.faction {
+      Faction::Boss => stats.hits_landed += 1,
+      Faction::Player => stats.damage_taken += event.amount,
+    }
+  }
+}
+
+/// Counts the dashes that went through an attack, once per dash.
+fn count_perfect_dodges(
+  mut damage_events: EventReader<DamageEvent>,
+  player: Query<(Entity, Has<Dashing>), With<Player>>,
+  mut stats: ResMut<FightStats>,
+  mut dodged: Local<bool>,
+) {
+  let Ok((player, dashing)) = player.get_single() else {
+    return;
+  };
+
+  let targeted = damage_events.read().any(|event| event.target == player);
+
+  if !dashing {
+    *dodged = false;
+    return;
+  }
+
+  if targeted && !*dodged {
+    *dodged = true;
+    stats.perfect_dodges += 1;
+  }
+}
+
+/// Scores the fight when the boss dies.
+fn score_fight(
+  mut death_events: EventReader<DeathEvent>,
+  bosses: Query<&Boss>,
+  stats: Res<FightStats>,
+  scoring: Res<ScoringAssets>,
+  rules: Res<Assets<ScoringRules>>,
+  mut result: ResMut<RunResult>,
+) {
+  let Some(rules) = rules.get(&scoring.rules) else {
+    return;
+  };
+
+  for event in death_events.read() {
+    let Ok(boss) = bosses.get(event.entity) else {
+      continue;
+    };
+
+    let score = rules.score(&stats);
+
+    result.fights.push(FightScore {
+      boss: boss.name.clone(),
+      clear_time: stats.clear_time,
+      damage_taken: stats.damage_taken,
+      hits_landed: stats.hits_landed,
+      perfect_dodges: stats.perfect_dodges,
+      no_hit: stats.damage_taken <= 0.0,
+      score,
+      rank: rules.rank(score),
+    });
+  }
+}
+
+/// Shows the score of the fight just won, next to the intermission banner.
+fn spawn_fight_results(
+  mut commands: Commands,
+  ui: Res<assets::UiAssets>,
+  result: Res<RunResult>,
+) {
+  let Some(fight) = result.fights.last() else {
+    return;
+  };
+
+  let text_font = TextFont {
+    font: ui.font.clone(),
+    font_size: 20.,
+    ..Default::default()
+  };
+
+  commands
+    .spawn((
+      Name::new("FightResults"),
+      StateScoped(FightState::Intermission),
+      Node {
+        width: Val::Percent(100.),
+        position_type: PositionType::Absolute,
+        top: Val::Percent(45.),
+        flex_direction: FlexDirection::Column,
+        align_items: AlignItems::Center,
+        row_gap: Val::Px(6.),
+        ..Default::default()
+      },
+    ))
+    .with_children(|parent| {
+      let lines = [
+        LocalizedText::new("score.clear_time").with_arg(
+          "time",
+          format_time(fight.clear_time, false),
+        ),
+        LocalizedText::new("score.damage_taken").with_arg(
+          "damage",
+          format!("{:.1}", fight.damage_taken),
+        ),
+        LocalizedText::new("score.hits_landed")
+          .with_arg("hits", fight.hits_landed.to_string()),
+        LocalizedText::new("score.perfect_dodges").with_arg(
+          "dodges",
+          fight.perfect_dodges.to_string(),
+        ),
+      ];
+
+      for line in lines {
+        parent.spawn((line, text_font.clone()));
+      }
+
+      if fight.no_hit {
+        parent.spawn((
+          LocalizedText::new("score.no_hit"),
+          text_font.clone(),
+        ));
+      }
+
+      parent.spawn((
+        LocalizedText::new("score.total")
+          .with_arg("score", fight.score.to_string()),
+        text_font.clone(),
+      ));
+      parent.spawn((
+        LocalizedText::new("score.rank").with_arg("rank", fight.rank.label()),
+        TextFont {
+          font_size: 40.,
+          ..text_font.clone()
+        },
+      ));
+    });
+}
+
+#[derive(Default)]
+pub struct ScoringRulesLoader;
+
+#[derive(Debug, Error)]
+pub enum ScoringRulesLoaderError {
+  #[error("could not read scoring rules: {0}")]
+  Io(#[from] std::io::Error),
+  #[error("could not parse scoring rules: {0}")]
+  Ron(#[from] ron::error::SpannedError),
+}
+
+impl AssetLoader for ScoringRulesLoader {
+  type Asset = ScoringRules;
+  type Settings = ();
+  type Error = ScoringRulesLoaderError;
+
+  async fn load(
+    &self,
+    reader: &mut dyn Reader,
+    _settings: &(),
+    _load_context: &mut LoadContext<'_>,
+  ) -> Result<Self::Asset, Self::Error> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).await?;
+
+    Ok(ron::de::from_bytes(&bytes)?)
+  }
+
+  fn extensions(&self) -> &[&str] {
+    &["scoring.ron"]
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn scoring_rules(ranks: Vec<RankThreshold>) -> ScoringRules {
+    ScoringRules {
+      clear_points: 1000.0,
+      time_penalty: 10.0,
+      damage_penalty: 50.0,
+      hit_points: 5.0,
+      perfect_dodge_points: 20.0,
+      no_hit_bonus: 300.0,
+      ranks,
+    }
+  }
+
+  fn threshold(rank: Rank, score: u32) -> RankThreshold {
+    RankThreshold { rank, score }
+  }
+
+  #[test]
+  fn score_adds_up_every_stat() {
+    let stats = FightStats {
+      clear_time: 30.0,
+      damage_taken: 2.0,
+      hits_landed: 10,
+      perfect_dodges: 3,
+    };
+
+    // 1000 - 300 + 50 + 60 - 100
+    assert_eq!(
+      scoring_rules(Vec::new()).score(&stats),
+      710
+    );
+  }
+
+  #[test]
+  fn score_adds_the_no_hit_bonus_without_damage() {
+    let stats = FightStats {
+      clear_time: 30.0,
+      ..Default::default()
+    };
+
+    assert_eq!(
+      scoring_rules(Vec::new()).score(&stats),
+      1000
+    );
+  }
+
+  #[test]
+  fn score_stops_at_zero() {
+    let stats = FightStats {
+      clear_time: 500.0,
+      damage_taken: 10.0,
+      ..Default::default()
+    };
+
+    assert_eq!(
+      scoring_rules(Vec::new()).score(&stats),
+      0
+    );
+  }
+
+  #[test]
+  fn rank_falls_back_to_c() {
+    let rules = scoring_rules(vec![
+      threshold(Rank::S, 1000),
+      threshold(Rank::A, 700),
+    ]);
+
+    assert_eq!(rules.rank(699), Rank::C);
+    assert_eq!(
+      scoring_rules(Vec::new()).rank(5000),
+      Rank::C
+    );
+  }
+
+  #[test]
+  fn rank_takes_the_highest_threshold_met_in_any_order() {
+    let rules = scoring_rules(vec![
+      threshold(Rank::B, 400),
+      threshold(Rank::S, 1000),
+      threshold(Rank::A, 700),
+    ]);
+
+    assert_eq!(rules.rank(400), Rank::B);
+    assert_eq!(rules.rank(999), Rank::A);
+    assert_eq!(rules.rank(1000), Rank::S);
+  }
+}
@@ -0,0 +1,84 @@
+use bevy::transform::TransformSystem;
+
+use crate::{prelude::*, settings::Settings};
+
+/// Largest offset of the camera, in pixels, at full trauma.
+const MAX_SHAKE_OFFSET: f32 = 12.0;
+/// Trauma lost per second.
+const TRAUMA_DECAY: f32 = 1.5;
+/// How many times per second the shake changes direction.
+const SHAKE_FREQUENCY: f32 = 25.0;
+
+pub struct ScreenShakePlugin;
+
+impl Plugin for ScreenShakePlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .init_resource::<ScreenShake>()
+      .add_systems(
+        OnEnter(AppState::InGame),
+        reset_screen_shake,
+      )
+      .add_systems(PreUpdate, remove_shake_offset)
+      .add_systems(
+        PostUpdate,
+        apply_screen_shake
+          .before(TransformSystem::TransformPropagate)
+          .run_if(in_state(AppState::InGame)),
+      );
+  }
+}
+
+/// Shakes the game camera, more strongly the more trauma is added.
+/// The offset is only applied between [`Update`] and rendering, so the
+/// camera systems never see it.
+#[derive(Resource, Default)]
+pub struct ScreenShake {
+  trauma: f32,
+  offset: Vec2,
+}
+
+impl ScreenShake {
+  /// Adds trauma, between `0.0` and `1.0`.
+  pub fn add_trauma(&mut self, amount: f32) {
+    self.trauma = (self.trauma + amount).min(1.0);
+  }
+}
+
+fn reset_screen_shake(mut shake: ResMut<ScreenShake>) {
+  *shake = ScreenShake::default();
+}
+
+fn remove_shake_offset(
+  mut shake: ResMut<ScreenShake>,
+  mut cameras: Query<&mut Transform, With<Camera2d>>,
+) {
+  if shake.offset == Vec2::ZERO {
+    return;
+  }
+
+  for mut transform in &mut cameras {
+    transform.translation -= shake.offset.extend(0.);
+  }
+
+  shake.offset = Vec2::ZERO;
+}
+
+fn apply_screen_shake(
+  time: Res<Time>,
+  settings: Res<Settings>,
+  mut shake: ResMut<ScreenShake>,
+  mut cameras: Query<&mut Transform, With<Camera2d>>,
+) {
+  shake.trauma = (shake.trauma - TRAUMA_DECAY * time.delta_secs()).max(0.0);
+
+  // Squared, so small hits only nudge the camera.
+  let strength =
+    shake.trauma.powi(2) * settings.screen_shake * MAX_SHAKE_OFFSET;
+  let phase = time.elapsed_secs() * SHAKE_FREQUENCY;
+  shake.offset = Vec2::new(phase.sin(), (phase * 1.3 + 1.7).cos()) * strength;
+
+  for mut transform in &mut cameras {
+    transform.translation += shake.offset.extend(0.);
+  }
+}
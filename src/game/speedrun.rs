@@ -0,0 +1,286 @@
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  difficulty::DifficultyLevel,
+  prelude::*,
+  results::RunResult,
+  settings::{Settings, TimingMethod},
+  storage,
+};
+
+use super::boss::Boss;
+use super::combat::{CombatSystems, DeathEvent};
+use super::rush::RushSystems;
+
+const PERSONAL_BESTS_FILE: &str = "personal_bests.ron";
+
+pub struct SpeedrunPlugin;
+
+impl Plugin for SpeedrunPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .insert_resource(PersonalBests::load())
+      .add_systems(
+        OnEnter(AppState::InGame),
+        start_run_timer,
+      )
+      .add_systems(
+        Update,
+        (
+          tick_run_timer,
+          record_splits.after(CombatSystems).before(RushSystems),
+        )
+          .chain()
+          .run_if(in_state(AppState::InGame)),
+      );
+  }
+}
+
+/// Times of the current run, in seconds, on both clocks.
+#[derive(Resource, Default)]
+pub struct RunTimer {
+  game_time: f32,
+  real_time: f32,
+  /// One split per boss defeated, in order.
+  pub splits: Vec<Split>,
+}
+
+impl RunTimer {
+  pub fn time(&self, method: TimingMethod) -> f32 {
+    match method {
+      TimingMethod::GameTime => self.game_time,
+      TimingMethod::RealTime => self.real_time,
+    }
+  }
+}
+
+/// The run times at which a boss was defeated.
+pub struct Split {
+  pub boss: String,
+  game_time: f32,
+  real_time: f32,
+  /// Difference with the personal best at the same boss on the clock in use,
+  /// negative when ahead.
+  pub delta: Option<f32>,
+}
+
+impl Split {
+  pub fn time(&self, method: TimingMethod) -> f32 {
+    match method {
+      TimingMethod::GameTime => self.game_time,
+      TimingMethod::RealTime => self.real_time,
+    }
+  }
+}
+
+/// Split times of the best complete run of every difficulty, kept
+/// separately for each [`TimingMethod`]. Runs with assists or at a reduced
+/// game speed don't count, see [`RunResult::ranked`].
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub struct PersonalBests(HashMap<DifficultyLevel, BestSplits>);
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct BestSplits {
+  game_time: Vec<f32>,
+  real_time: Vec<f32>,
+}
+
+impl BestSplits {
+  fn get_mut(&mut self, method: TimingMethod) -> &mut Vec<f32> {
+    match method {
+      TimingMethod::GameTime => &mut self.game_time,
+      TimingMethod::RealTime => &mut self.real_time,
+    }
+  }
+}
+
+impl PersonalBests {
+  /// The personal best split times, empty if there is no complete run yet.
+  fn splits(&self, level: DifficultyLevel, method: TimingMethod) -> &[f32] {
+    self
+      .0
+      .get(&level)
+      .map(|best| match method {
+        TimingMethod::GameTime => &best.game_time[..],
+        TimingMethod::RealTime => &best.real_time[..],
+      })
+      .unwrap_or(&[])
+  }
+
+  fn delta(
+    &self,
+    level: DifficultyLevel,
+    method: TimingMethod,
+    index: usize,
+    time: f32,
+  ) -> Option<f32> {
+    self
+      .splits(level, method)
+      .get(index)
+      .map(|best| time - best)
+  }
+
+  /// Keeps the splits of a complete run on every clock it improves,
+  /// returning the clocks it did.
+  fn submit(
+    &mut self,
+    result: &RunResult,
+    timer: &RunTimer,
+  ) -> Vec<TimingMethod> {
+    if !result.ranked() {
+      return Vec::new();
+    }
+
+    let best = self.0.entry(result.difficulty.level).or_default();
+
+    enum_iterator::all::<TimingMethod>()
+      .filter(|method| {
+        let splits = best.get_mut(*method);
+        let improved =
+          splits.last().is_none_or(|best| timer.time(*method) < *best);
+
+        if improved {
+          *splits = timer
+            .splits
+            .iter()
+            .map(|split| split.time(*method))
+            .collect();
+        }
+
+        improved
+      })
+      .collect()
+  }
+
+  /// Loads the stored personal bests, starting over if they are unreadable.
+  fn load() -> Self {
+    let contents = match storage::read(PERSONAL_BESTS_FILE) {
+      Ok(contents) => contents,
+      Err(error) => {
+        warn!("{error}, personal bests are not available");
+        return Self::default();
+      }
+    };
+
+    contents
+      .and_then(|contents| {
+        ron::from_str(&contents)
+          .inspect_err(|error| warn!("could not parse personal bests: {error}"))
+          .ok()
+      })
+      .unwrap_or_default()
+  }
+
+  fn save(&self) {
+    let contents = match ron::ser::to_string_pretty(self, Default::default()) {
+      Ok(contents) => contents,
+      Err(error) => {
+        warn!("could not serialize personal bests: {error}");
+        return;
+      }
+    };
+
+    if let Err(error) = storage::write(PERSONAL_BESTS_FILE, &contents) {
+      warn!("{error}");
+    }
+  }
+}
+
+/// Formats seconds as `m:ss.cc`, with a sign when `signed`.
+pub fn format_time(seconds: f32, signed: bool) -> String {
+  let sign = match (signed, seconds < 0.0) {
+    (true, true) => "-",
+    (true, false) => "+",
+    (false, _) => "",
+  };
+
+  let centiseconds = (seconds.abs() * 100.0).floor() as u32;
+  let minutes = centiseconds / 6000;
+  let seconds = centiseconds / 100 % 60;
+  let centiseconds = centiseconds % 100;
+
+  format!("{sign}{minutes}:{seconds:02}.{centiseconds:02}")
+}
+
+fn start_run_timer(mut commands: Commands) {
+  commands.insert_resource(RunTimer::default());
+}
+
+/// Runs both clocks for as long as the run lasts. Virtual time doesn't
+/// advance while the game is paused.
+fn tick_run_timer(
+  virtual_time: Res<Time<Virtual>>,
+  real_time: Res<Time<Real>>,
+  mut timer: ResMut<RunTimer>,
+) {
+  timer.game_time += virtual_time.delta_secs();
+  timer.real_time += real_time.delta_secs();
+}
+
+/// Records a split for every boss killed, and submits the splits of the
+/// run once the last one is down.
+fn record_splits(
+  mut death_events: EventReader<DeathEvent>,
+  bosses: Query<&Boss>,
+  settings: Res<Settings>,
+  mut timer: ResMut<RunTimer>,
+  mut result: ResMut<RunResult>,
+  mut personal_bests: ResMut<PersonalBests>,
+) {
+  for event in death_events.read() {
+    let Ok(boss) = bosses.get(event.entity) else {
+      continue;
+    };
+
+    let method = settings.timing;
+    let split = Split {
+      boss: boss.name.clone(),
+      game_time: timer.game_time,
+      real_time: timer.real_time,
+      delta: match result.ranked() {
+        true => personal_bests.delta(
+          result.difficulty.level,
+          method,
+          timer.splits.len(),
+          timer.time(method),
+        ),
+        false => None,
+      },
+    };
+    timer.splits.push(split);
+
+    if timer.splits.len() < result.boss_count {
+      continue;
+    }
+
+    let improved = personal_bests.submit(&result, &timer);
+
+    result.time = Some(timer.time(method));
+    result.personal_best = improved.contains(&method);
+
+    if !improved.is_empty() {
+      personal_bests.save();
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn format_time_rolls_over_to_minutes() {
+    assert_eq!(format_time(59.999, false), "0:59.99");
+    assert_eq!(format_time(60.0, false), "1:00.00");
+    assert_eq!(format_time(125.75, false), "2:05.75");
+  }
+
+  #[test]
+  fn format_time_signs_deltas() {
+    assert_eq!(format_time(1.5, true), "+0:01.50");
+    assert_eq!(format_time(-2.25, true), "-0:02.25");
+    assert_eq!(format_time(-61.5, true), "-1:01.50");
+  }
+}
@@ -0,0 +1,321 @@
+use std::f32::consts::FRAC_PI_2;
+
+use serde::Deserialize;
+
+use crate::{prelude::*, settings::Settings};
+
+use super::combat::{DamageEvent, Faction, Health};
+
+/// Seconds the hit area stays visible after a telegraph resolves.
+const HIT_AREA_DURATION: f32 = 0.15;
+/// Depth of the fill, just above the rest of the telegraph.
+const FILL_DEPTH: f32 = 0.1;
+const AREA_ALPHA: f32 = 0.15;
+const FILL_ALPHA: f32 = 0.35;
+
+pub struct TelegraphPlugin;
+
+impl Plugin for TelegraphPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .add_systems(Startup, setup_telegraph_assets)
+      .add_systems(
+        Update,
+        update_telegraph_palette.run_if(resource_changed::<Settings>),
+      )
+      .add_systems(
+        Update,
+        (
+          spawn_telegraph_visuals,
+          fill_telegraphs,
+          resolve_telegraphs,
+          despawn_hit_areas,
+        )
+          .chain()
+          .run_if(in_state(InGameState::Running)),
+      );
+  }
+}
+
+/// The area covered by a telegraph, oriented along the local `x` axis.
+#[derive(Debug, Clone, Deserialize)]
+pub enum TelegraphShape {
+  Circle {
+    radius: f32,
+  },
+  Rectangle {
+    width: f32,
+    height: f32,
+  },
+  /// A circular sector `angle` degrees wide.
+  Cone {
+    radius: f32,
+    angle: f32,
+  },
+  /// A line starting at the telegraph position.
+  Line {
+    length: f32,
+    width: f32,
+  },
+}
+
+impl TelegraphShape {
+  fn mesh(&self) -> Mesh {
+    match *self {
+      Self::Circle { radius } => Circle::new(radius).into(),
+      Self::Rectangle { width, height } => Rectangle::new(width, height).into(),
+      Self::Cone { radius, angle } => {
+        CircularSector::from_degrees(radius, angle).into()
+      }
+      Self::Line { length, width } => Rectangle::new(length, width).into(),
+    }
+  }
+
+  /// Transform of the shape mesh inside the telegraph, for the given
+  /// fill `progress` between `0.0` and `1.0`.
+  fn mesh_transform(&self, progress: f32) -> Transform {
+    match *self {
+      // Sectors are built around the `y` axis.
+      Self::Cone { .. } => {
+        Transform::from_rotation(Quat::from_rotation_z(-FRAC_PI_2))
+          .with_scale(Vec3::splat(progress))
+      }
+      // Lines grow from their start towards their end.
+      Self::Line { length, .. } => {
+        Transform::from_xyz(length * progress / 2.0, 0.0, 0.0)
+          .with_scale(Vec3::new(progress, 1.0, 1.0))
+      }
+      _ => Transform::from_scale(Vec3::splat(progress)),
+    }
+  }
+
+  /// Whether a point, in the local space of the telegraph, is inside the
+  /// shape.
+  fn contains(&self, point: Vec2) -> bool {
+    match *self {
+      Self::Circle { radius } => point.length() <= radius,
+      Self::Rectangle { width, height } => {
+        point.x.abs() <= width / 2.0 && point.y.abs() <= height / 2.0
+      }
+      Self::Cone { radius, angle } => {
+        point.length() <= radius
+          && point.to_angle().abs() <= angle.to_radians() / 2.0
+      }
+      Self::Line { length, width } => {
+        (0.0..=length).contains(&point.x) && point.y.abs() <= width / 2.0
+      }
+    }
+  }
+}
+
+/// Where a boss places the telegraph of an attack.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub enum TelegraphAnchor {
+  #[default]
+  Boss,
+  Player,
+}
+
+/// A telegraphed area attack, as written in a boss definition.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelegraphAttack {
+  pub shape: TelegraphShape,
+  /// Seconds between the warning and the hit. Boss definitions reject
+  /// negative values.
+  pub windup: f32,
+  #[serde(default = "default_damage")]
+  pub damage: f32,
+  #[serde(default)]
+  pub anchor: TelegraphAnchor,
+}
+
+fn default_damage() -> f32 {
+  1.0
+}
+
+/// A warning drawn on the arena floor that fills up during its wind-up,
+/// then damages every entity of another [`Faction`] inside its shape.
+#[derive(Component)]
+pub struct Telegraph {
+  pub shape: TelegraphShape,
+  pub damage: f32,
+  pub faction: Faction,
+  windup: Timer,
+}
+
+impl Telegraph {
+  pub fn new(
+    shape: TelegraphShape,
+    windup: f32,
+    damage: f32,
+    faction: Faction,
+  ) -> Self {
+    Self {
+      shape,
+      damage,
+      faction,
+      windup: Timer::from_seconds(windup, TimerMode::Once),
+    }
+  }
+}
+
+/// The part of a telegraph that grows with its wind-up.
+#[derive(Component)]
+struct TelegraphFill;
+
+/// A resolved telegraph, kept on screen for a moment before despawning.
+#[derive(Component, Deref, DerefMut)]
+struct HitArea(Timer);
+
+#[derive(Resource)]
+struct TelegraphAssets {
+  area: Handle<ColorMaterial>,
+  fill: Handle<ColorMaterial>,
+  hit: Handle<ColorMaterial>,
+}
+
+fn fill_transform(shape: &TelegraphShape, progress: f32) -> Transform {
+  let mut transform = shape.mesh_transform(progress);
+  transform.translation.z = FILL_DEPTH;
+  transform
+}
+
+fn setup_telegraph_assets(
+  mut commands: Commands,
+  settings: Res<Settings>,
+  mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+  let color = settings.palette.telegraph();
+
+  commands.insert_resource(TelegraphAssets {
+    area: materials.add(color.with_alpha(AREA_ALPHA)),
+    fill: materials.add(color.with_alpha(FILL_ALPHA)),
+    hit: materials.add(Color::srgba(1.0, 0.9, 0.8, 0.8)),
+  });
+}
+
+fn update_telegraph_palette(
+  settings: Res<Settings>,
+  assets: Res<TelegraphAssets>,
+  mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+  let color = settings.palette.telegraph();
+
+  for (handle, alpha) in
+    [(&assets.area, AREA_ALPHA), (&assets.fill, FILL_ALPHA)]
+  {
+    if let Some(material) = materials.get_mut(handle) {
+      material.color = color.with_alpha(alpha);
+    }
+  }
+}
+
+fn spawn_telegraph_visuals(
+  mut commands: Commands,
+  mut meshes: ResMut<Assets<Mesh>>,
+  assets: Res<TelegraphAssets>,
+  telegraphs: Query<(Entity, &Telegraph), Added<Telegraph>>,
+) {
+  for (entity, telegraph) in &telegraphs {
+    let mesh = meshes.add(telegraph.shape.mesh());
+
+    commands.entity(entity).with_children(|parent| {
+      parent.spawn((
+        Mesh2d(mesh.clone()),
+        MeshMaterial2d(assets.area.clone()),
+        telegraph.shape.mesh_transform(1.0),
+      ));
+      parent.spawn((
+        TelegraphFill,
+        Mesh2d(mesh),
+        MeshMaterial2d(assets.fill.clone()),
+        fill_transform(&telegraph.shape, 0.0),
+      ));
+    });
+  }
+}
+
+fn fill_telegraphs(
+  time: Res<Time>,
+  mut telegraphs: Query<(&mut Telegraph, &Children), Without<HitArea>>,
+  mut fills: Query<&mut Transform, With<TelegraphFill>>,
+) {
+  for (mut telegraph, children) in &mut telegraphs {
+    telegraph.windup.tick(time.delta());
+
+    let progress = telegraph.windup.fraction();
+
+    for child in children {
+      if let Ok(mut transform) = fills.get_mut(*child) {
+        *transform = fill_transform(&telegraph.shape, progress);
+      }
+    }
+  }
+}
+
+/// Damages the entities inside telegraphs whose wind-up has finished.
+fn resolve_telegraphs(
+  mut commands: Commands,
+  assets: Res<TelegraphAssets>,
+  settings: Res<Settings>,
+  telegraphs: Query<
+    (
+      Entity,
+      &Telegraph,
+      &GlobalTransform,
+      &Children,
+    ),
+    Without<HitArea>,
+  >,
+  mut fills: Query<&mut MeshMaterial2d<ColorMaterial>, With<TelegraphFill>>,
+  targets: Query<(Entity, &GlobalTransform, &Faction), With<Health>>,
+  mut damage_events: EventWriter<DamageEvent>,
+) {
+  for (entity, telegraph, transform, children) in &telegraphs {
+    if !telegraph.windup.finished() {
+      continue;
+    }
+
+    let to_local = transform.affine().inverse();
+
+    for (target, target_transform, faction) in &targets {
+      let point = to_local.transform_point3(target_transform.translation());
+
+      if *faction != telegraph.faction
+        && telegraph.shape.contains(point.truncate())
+      {
+        damage_events.send(DamageEvent {
+          target,
+          source: telegraph.faction,
+          amount: telegraph.damage,
+        });
+      }
+    }
+
+    // Without flashes the filled telegraph simply stays on screen.
+    if !settings.reduced_flashing {
+      for child in children {
+        if let Ok(mut material) = fills.get_mut(*child) {
+          material.0 = assets.hit.clone();
+        }
+      }
+    }
+
+    commands.entity(entity).insert(HitArea(Timer::from_seconds(
+      HIT_AREA_DURATION,
+      TimerMode::Once,
+    )));
+  }
+}
+
+fn despawn_hit_areas(
+  mut commands: Commands,
+  time: Res<Time>,
+  mut hit_areas: Query<(Entity, &mut HitArea)>,
+) {
+  for (entity, mut hit_area) in &mut hit_areas {
+    if hit_area.tick(time.delta()).finished() {
+      commands.entity(entity).despawn_recursive();
+    }
+  }
+}
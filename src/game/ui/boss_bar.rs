@@ -0,0 +1,228 @@
+use crate::prelude::*;
+
+use crate::game::boss::{Boss, BossPhases};
+use crate::game::combat::Health;
+
+const BAR_HEIGHT: f32 = 20.;
+const HEALTH_COLOR: Color = Color::srgb(0.8, 0.1, 0.15);
+/// Color of the health lost recently, before the chip catches up.
+const CHIP_COLOR: Color = Color::srgb(1.0, 0.85, 0.5);
+const EMPTY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+const NOTCH_COLOR: Color = Color::srgb(0.95, 0.95, 0.95);
+/// Seconds the chip waits after a hit before draining.
+const CHIP_DELAY: f32 = 0.6;
+/// Fraction of the bar the chip drains per second.
+const CHIP_SPEED: f32 = 0.5;
+
+pub struct BossBarPlugin;
+
+impl Plugin for BossBarPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .add_systems(
+        OnEnter(AppState::InGame),
+        spawn_boss_bar,
+      )
+      .add_systems(
+        Update,
+        (
+          show_boss_bar,
+          update_boss_name,
+          update_phase_notches,
+          update_boss_health,
+        )
+          .chain()
+          .run_if(in_state(AppState::InGame)),
+      );
+  }
+}
+
+#[derive(Component)]
+struct BossBar;
+
+#[derive(Component)]
+struct BossBarName;
+
+/// The track of the bar, holding the fills and the notches.
+#[derive(Component)]
+struct BossBarTrack;
+
+#[derive(Component)]
+struct BossHealthFill;
+
+/// Trails behind the [`BossHealthFill`], so the damage of a hit stays visible
+/// for a moment before draining.
+#[derive(Component)]
+struct BossChipFill {
+  /// Fraction of the bar currently covered.
+  fraction: f32,
+  delay: Timer,
+}
+
+/// Marks the health threshold of a [`BossPhases`] entry.
+#[derive(Component)]
+struct PhaseNotch;
+
+fn spawn_boss_bar(mut commands: Commands, ui: Res<assets::UiAssets>) {
+  commands
+    .spawn((
+      Name::new("BossBar"),
+      BossBar,
+      StateScoped(AppState::InGame),
+      Visibility::Hidden,
+      Node {
+        position_type: PositionType::Absolute,
+        bottom: Val::Px(32.),
+        left: Val::Percent(20.),
+        width: Val::Percent(60.),
+        flex_direction: FlexDirection::Column,
+        align_items: AlignItems::Center,
+        row_gap: Val::Px(6.),
+        ..Default::default()
+      },
+    ))
+    .with_children(|parent| {
+      parent.spawn((
+        BossBarName,
+        Text::default(),
+        TextFont {
+          font: ui.font.clone(),
+          font_size: 24.,
+          ..Default::default()
+        },
+      ));
+
+      parent
+        .spawn((
+          BossBarTrack,
+          Node {
+            width: Val::Percent(100.),
+            height: Val::Px(BAR_HEIGHT),
+            ..Default::default()
+          },
+          BackgroundColor(EMPTY_COLOR),
+        ))
+        .with_children(|track| {
+          track.spawn((
+            BossChipFill {
+              fraction: 1.0,
+              delay: Timer::from_seconds(CHIP_DELAY, TimerMode::Once),
+            },
+            fill_node(1.0),
+            BackgroundColor(CHIP_COLOR),
+          ));
+          track.spawn((
+            BossHealthFill,
+            fill_node(1.0),
+            BackgroundColor(HEALTH_COLOR),
+          ));
+        });
+    });
+}
+
+/// A fill covering `fraction` of the track from the left, stacked on the
+/// other fills.
+fn fill_node(fraction: f32) -> Node {
+  Node {
+    position_type: PositionType::Absolute,
+    width: Val::Percent(fraction * 100.),
+    height: Val::Percent(100.),
+    ..Default::default()
+  }
+}
+
+/// Shows the bar only while a boss is around.
+fn show_boss_bar(
+  bosses: Query<(), With<Boss>>,
+  mut bars: Query<&mut Visibility, With<BossBar>>,
+) {
+  let visibility = match bosses.is_empty() {
+    true => Visibility::Hidden,
+    false => Visibility::Inherited,
+  };
+
+  for mut bar in &mut bars {
+    bar.set_if_neq(visibility);
+  }
+}
+
+fn update_boss_name(
+  bosses: Query<&Boss, Changed<Boss>>,
+  mut names: Query<&mut Text, With<BossBarName>>,
+) {
+  let Ok(boss) = bosses.get_single() else {
+    return;
+  };
+
+  for mut name in &mut names {
+    name.0.clone_from(&boss.name);
+  }
+}
+
+/// Places a notch at the threshold of every phase but the first one.
+fn update_phase_notches(
+  mut commands: Commands,
+  bosses: Query<&BossPhases, Changed<BossPhases>>,
+  tracks: Query<Entity, With<BossBarTrack>>,
+  notches: Query<Entity, With<PhaseNotch>>,
+) {
+  let Ok(phases) = bosses.get_single() else {
+    return;
+  };
+
+  for notch in &notches {
+    commands.entity(notch).despawn_recursive();
+  }
+
+  for track in &tracks {
+    commands.entity(track).with_children(|track| {
+      for phase in phases.phases.iter().skip(1) {
+        track.spawn((
+          PhaseNotch,
+          Node {
+            position_type: PositionType::Absolute,
+            left: Val::Percent(phase.threshold * 100.),
+            width: Val::Px(2.),
+            height: Val::Percent(100.),
+            ..Default::default()
+          },
+          BackgroundColor(NOTCH_COLOR),
+        ));
+      }
+    });
+  }
+}
+
+fn update_boss_health(
+  time: Res<Time>,
+  bosses: Query<Ref<Health>, With<Boss>>,
+  mut health_fills: Query<&mut Node, With<BossHealthFill>>,
+  mut chip_fills: Query<
+    (&mut BossChipFill, &mut Node),
+    Without<BossHealthFill>,
+  >,
+) {
+  let Ok(health) = bosses.get_single() else {
+    return;
+  };
+
+  let fraction = health.fraction();
+
+  for mut node in &mut health_fills {
+    node.width = Val::Percent(fraction * 100.);
+  }
+
+  for (mut chip, mut node) in &mut chip_fills {
+    if fraction > chip.fraction {
+      // A new boss, or healing: nothing to trail.
+      chip.fraction = fraction;
+    } else if health.is_changed() && fraction < chip.fraction {
+      chip.delay.reset();
+    } else if chip.delay.tick(time.delta()).finished() {
+      chip.fraction =
+        (chip.fraction - CHIP_SPEED * time.delta_secs()).max(fraction);
+    }
+
+    node.width = Val::Percent(chip.fraction * 100.);
+  }
+}
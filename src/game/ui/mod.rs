@@ -0,0 +1,139 @@
+mod boss_bar;
+mod player_hud;
+
+use crate::{prelude::*, settings::Settings};
+
+use super::speedrun::{format_time, RunTimer};
+
+const AHEAD_COLOR: Color = Color::srgb(0.3, 0.9, 0.4);
+const BEHIND_COLOR: Color = Color::srgb(0.95, 0.3, 0.3);
+
+/// Text of the run time.
+#[derive(Component)]
+struct RunTime;
+
+/// The column the splits of the run are added to.
+#[derive(Component)]
+struct SplitList;
+
+pub struct UiPlugin;
+
+impl Plugin for UiPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .add_plugins((
+        player_hud::PlayerHudPlugin,
+        boss_bar::BossBarPlugin,
+      ))
+      .add_systems(OnEnter(AppState::InGame), spawn_timer)
+      .add_systems(
+        Update,
+        (update_run_time, add_splits).run_if(in_state(AppState::InGame)),
+      );
+  }
+}
+
+fn spawn_timer(mut commands: Commands, ui: Res<assets::UiAssets>) {
+  commands
+    .spawn((
+      Name::new("RunTimer"),
+      StateScoped(AppState::InGame),
+      Node {
+        display: Display::Flex,
+        flex_direction: FlexDirection::Column,
+        align_items: AlignItems::FlexEnd,
+        position_type: PositionType::Absolute,
+        top: Val::Px(0.),
+        right: Val::Px(0.),
+        row_gap: Val::Px(10.),
+        padding: UiRect::all(Val::Px(20.0)),
+        ..default()
+      },
+    ))
+    .with_children(|builder| {
+      builder.spawn((
+        Text::default(),
+        TextFont {
+          font: ui.font.clone(),
+          font_size: 28.,
+          ..default()
+        },
+        RunTime,
+      ));
+
+      builder.spawn((
+        SplitList,
+        Node {
+          flex_direction: FlexDirection::Column,
+          align_items: AlignItems::FlexEnd,
+          row_gap: Val::Px(4.),
+          ..default()
+        },
+      ));
+    });
+}
+
+/// Shows the time of the run on the clock picked in the settings.
+fn update_run_time(
+  settings: Res<Settings>,
+  timer: Res<RunTimer>,
+  mut query: Query<&mut Text, With<RunTime>>,
+) {
+  for mut text in &mut query {
+    **text = format_time(timer.time(settings.timing), false);
+  }
+}
+
+/// Adds a row for every new split, with its delta against the personal
+/// best.
+fn add_splits(
+  mut commands: Commands,
+  ui: Res<assets::UiAssets>,
+  settings: Res<Settings>,
+  timer: Res<RunTimer>,
+  lists: Query<(Entity, Option<&Children>), With<SplitList>>,
+) {
+  let text_font = TextFont {
+    font: ui.font.clone(),
+    font_size: 16.,
+    ..default()
+  };
+
+  for (list, rows) in &lists {
+    let shown = rows.map_or(0, |rows| rows.len());
+
+    for split in timer.splits.iter().skip(shown) {
+      commands.entity(list).with_children(|list| {
+        list
+          .spawn(Node {
+            column_gap: Val::Px(12.),
+            ..default()
+          })
+          .with_children(|row| {
+            row.spawn((
+              Text::new(&split.boss),
+              text_font.clone(),
+            ));
+            row.spawn((
+              Text::new(format_time(
+                split.time(settings.timing),
+                false,
+              )),
+              text_font.clone(),
+            ));
+
+            if let Some(delta) = split.delta {
+              row.spawn((
+                Text::new(format_time(delta, true)),
+                text_font.clone(),
+                TextColor(match delta <= 0.0 {
+                  true => AHEAD_COLOR,
+                  false => BEHIND_COLOR,
+                }),
+              ));
+            }
+          });
+      });
+    }
+  }
+}
@@ -0,0 +1,204 @@
+use crate::{difficulty::Difficulty, prelude::*};
+
+use crate::game::combat::Health;
+use crate::game::player::{Dash, Dashing, MeleeAttack, Player};
+
+const HEART_SIZE: f32 = 24.;
+const HEART_COLOR: Color = Color::srgb(0.85, 0.15, 0.2);
+const COOLDOWN_COLOR: Color = Color::srgb(0.45, 0.45, 0.5);
+const READY_COLOR: Color = Color::srgb(0.95, 0.95, 0.8);
+const EMPTY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.5);
+
+pub struct PlayerHudPlugin;
+
+impl Plugin for PlayerHudPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .add_systems(
+        OnEnter(AppState::InGame),
+        spawn_player_hud,
+      )
+      .add_systems(
+        Update,
+        (update_hearts, update_cooldowns).run_if(in_state(AppState::InGame)),
+      );
+  }
+}
+
+/// The row holding one [`Heart`] per hit point of the player.
+#[derive(Component)]
+struct Hearts;
+
+/// A hit point of the player, filled as much as it is left.
+#[derive(Component)]
+struct Heart(usize);
+
+#[derive(Component)]
+struct HeartFill;
+
+#[derive(Component, Clone, Copy)]
+enum Cooldown {
+  Dash,
+  Attack,
+}
+
+#[derive(Component)]
+struct CooldownFill;
+
+fn spawn_player_hud(mut commands: Commands, ui: Res<assets::UiAssets>) {
+  let text_font = TextFont {
+    font: ui.font.clone(),
+    font_size: 16.,
+    ..Default::default()
+  };
+
+  commands
+    .spawn((
+      Name::new("PlayerHud"),
+      StateScoped(AppState::InGame),
+      Node {
+        position_type: PositionType::Absolute,
+        top: Val::Px(0.),
+        left: Val::Px(0.),
+        flex_direction: FlexDirection::Column,
+        row_gap: Val::Px(10.),
+        padding: UiRect::all(Val::Px(20.)),
+        ..Default::default()
+      },
+    ))
+    .with_children(|parent| {
+      parent.spawn((
+        Hearts,
+        Node {
+          column_gap: Val::Px(6.),
+          ..Default::default()
+        },
+      ));
+
+      for (cooldown, key) in [
+        (Cooldown::Dash, "hud.dash"),
+        (Cooldown::Attack, "hud.attack"),
+      ] {
+        parent
+          .spawn(Node {
+            column_gap: Val::Px(8.),
+            align_items: AlignItems::Center,
+            ..Default::default()
+          })
+          .with_children(|row| {
+            row
+              .spawn((
+                cooldown,
+                Node {
+                  width: Val::Px(64.),
+                  height: Val::Px(8.),
+                  ..Default::default()
+                },
+                BackgroundColor(EMPTY_COLOR),
+              ))
+              .with_child((
+                CooldownFill,
+                Node {
+                  height: Val::Percent(100.),
+                  ..Default::default()
+                },
+                BackgroundColor(READY_COLOR),
+              ));
+            row.spawn((
+              LocalizedText::new(key),
+              text_font.clone(),
+            ));
+          });
+      }
+    });
+}
+
+/// Keeps one heart per hit point of the player, and fills them with its
+/// current health.
+fn update_hearts(
+  mut commands: Commands,
+  player: Query<&Health, (With<Player>, Changed<Health>)>,
+  containers: Query<(Entity, Option<&Children>), With<Hearts>>,
+  hearts: Query<(&Heart, &Children)>,
+  mut fills: Query<&mut Node, With<HeartFill>>,
+) {
+  let Ok(health) = player.get_single() else {
+    return;
+  };
+
+  let count = health.max.ceil() as usize;
+
+  for (container, children) in &containers {
+    let children = children.map(|children| &children[..]).unwrap_or(&[]);
+
+    if children.len() != count {
+      commands.entity(container).despawn_descendants();
+      commands.entity(container).with_children(|parent| {
+        for index in 0..count {
+          parent
+            .spawn((
+              Heart(index),
+              Node {
+                width: Val::Px(HEART_SIZE),
+                height: Val::Px(HEART_SIZE),
+                ..Default::default()
+              },
+              BackgroundColor(EMPTY_COLOR),
+            ))
+            .with_child((
+              HeartFill,
+              Node {
+                width: Val::Percent(heart_fill(health, index)),
+                height: Val::Percent(100.),
+                ..Default::default()
+              },
+              BackgroundColor(HEART_COLOR),
+            ));
+        }
+      });
+      continue;
+    }
+
+    for (heart, heart_children) in hearts.iter_many(children) {
+      let mut fills = fills.iter_many_mut(heart_children);
+      while let Some(mut node) = fills.fetch_next() {
+        node.width = Val::Percent(heart_fill(health, heart.0));
+      }
+    }
+  }
+}
+
+/// Percentage of the heart at `index` left filled by the current health.
+fn heart_fill(health: &Health, index: usize) -> f32 {
+  (health.current - index as f32).clamp(0.0, 1.0) * 100.
+}
+
+fn update_cooldowns(
+  difficulty: Res<Difficulty>,
+  player: Query<(&Dash, Has<Dashing>, &MeleeAttack), With<Player>>,
+  indicators: Query<(&Cooldown, &Children)>,
+  mut fills: Query<(&mut Node, &mut BackgroundColor), With<CooldownFill>>,
+) {
+  let Ok((dash, dashing, melee)) = player.get_single() else {
+    return;
+  };
+
+  for (cooldown, children) in &indicators {
+    let readiness = match cooldown {
+      Cooldown::Dash if difficulty.assists.infinite_dash => 1.0,
+      // The cooldown only starts once the dash is over.
+      Cooldown::Dash if dashing => 0.0,
+      Cooldown::Dash => dash.readiness(),
+      Cooldown::Attack => melee.readiness(),
+    };
+
+    let mut fills = fills.iter_many_mut(children);
+    while let Some((mut node, mut color)) = fills.fetch_next() {
+      node.width = Val::Percent(readiness * 100.);
+      color.0 = match readiness >= 1.0 {
+        true => READY_COLOR,
+        false => COOLDOWN_COLOR,
+      };
+    }
+  }
+}
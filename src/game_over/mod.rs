@@ -1,15 +1,5 @@
 use crate::prelude::*;
-
-#[derive(Component)]
-pub struct StateOnPress<S: States> {
-  pub action: S,
-}
-
-impl<S: States> StateOnPress<S> {
-  fn from(state: S) -> Self {
-    Self { action: state }
-  }
-}
+use crate::results::{spawn_run_result, RunResult};
 
 pub struct GameOverPlugin<S: States> {
   pub state: S,
@@ -17,16 +7,18 @@ pub struct GameOverPlugin<S: States> {
 
 impl<S: States> Plugin for GameOverPlugin<S> {
   fn build(&self, app: &mut App) {
-    app
-      .add_systems(
-        OnEnter(self.state.clone()),
-        setup_game_over,
-      )
-      .add_systems(Update, action_on_press);
+    app.add_systems(
+      OnEnter(self.state.clone()),
+      setup_game_over,
+    );
   }
 }
 
-fn setup_game_over(mut commands: Commands) {
+fn setup_game_over(
+  mut commands: Commands,
+  res: Res<assets::UiAssets>,
+  result: Res<RunResult>,
+) {
   let container = commands
     .spawn((
       StateScoped(AppState::GameOver),
@@ -44,29 +36,27 @@ fn setup_game_over(mut commands: Commands) {
     ))
     .id();
 
+  let run_result = spawn_run_result(
+    &mut commands,
+    &TextFont {
+      font: res.font.clone(),
+      font_size: 24.,
+      ..Default::default()
+    },
+    &result,
+  );
+
   let play_again = commands
     .spawn((
       Button,
-      StateOnPress::from(AppState::InGame),
+      ButtonAction::set_state(AppState::InGame),
     ))
     .with_children(|parent| {
-      parent.spawn(Text::new("Play again"));
+      parent.spawn(LocalizedText::new("common.play_again"));
     })
     .id();
 
-  commands.entity(container).add_children(&[play_again]);
-}
-
-fn action_on_press(
-  mut interaction_query: Query<
-    (&Interaction, &StateOnPress<AppState>),
-    (Changed<Interaction>, With<Button>),
-  >,
-  mut next_state: ResMut<NextState<AppState>>,
-) {
-  for (interaction, state) in &mut interaction_query {
-    if interaction == &Interaction::Pressed {
-      next_state.set(state.action);
-    }
-  }
+  commands
+    .entity(container)
+    .add_children(&[run_result, play_again]);
 }
@@ -0,0 +1,384 @@
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent};
+use bevy::input::InputSystem;
+use bevy::utils::HashMap;
+use enum_iterator::{all, Sequence};
+use serde::{Deserialize, Serialize};
+
+use crate::{prelude::*, settings::Settings};
+
+/// Value above which an analog binding counts as pressed.
+const PRESS_THRESHOLD: f32 = 0.5;
+/// Stick values below this are ignored, the rest is rescaled to `0.0..=1.0`.
+const STICK_DEADZONE: f32 = 0.2;
+/// Seconds a controller connection notice stays on screen.
+const GAMEPAD_NOTICE_DURATION: f32 = 3.0;
+
+pub struct ActionsPlugin;
+
+impl Plugin for ActionsPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .init_resource::<ActionState>()
+      .register_type::<InputMap>()
+      .add_systems(
+        PreUpdate,
+        update_action_state.in_set(ActionSystem).after(InputSystem),
+      )
+      .add_systems(
+        Update,
+        (
+          show_gamepad_notices,
+          despawn_gamepad_notices,
+        ),
+      );
+  }
+}
+
+/// The system updating [`ActionState`], in [`PreUpdate`].
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActionSystem;
+
+/// Something the player can do, independently of the input device.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Hash,
+  Reflect,
+  Sequence,
+  Serialize,
+  Deserialize
+)]
+pub enum Action {
+  MoveUp,
+  MoveDown,
+  MoveLeft,
+  MoveRight,
+  Attack,
+  Dash,
+  Pause,
+  Confirm,
+  Back,
+}
+
+impl Action {
+  /// Key of the name of the action in the string tables.
+  pub fn text_key(self) -> &'static str {
+    match self {
+      Self::MoveUp => "action.move_up",
+      Self::MoveDown => "action.move_down",
+      Self::MoveLeft => "action.move_left",
+      Self::MoveRight => "action.move_right",
+      Self::Attack => "action.attack",
+      Self::Dash => "action.dash",
+      Self::Pause => "action.pause",
+      Self::Confirm => "action.confirm",
+      Self::Back => "action.back",
+    }
+  }
+}
+
+/// The kind of device a [`Binding`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputDevice {
+  KeyboardMouse,
+  Gamepad,
+}
+
+/// The direction of a gamepad axis that triggers an action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum AxisDirection {
+  Positive,
+  Negative,
+}
+
+/// A physical input bound to an [`Action`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum Binding {
+  Key(KeyCode),
+  Mouse(MouseButton),
+  GamepadButton(GamepadButton),
+  GamepadAxis(GamepadAxis, AxisDirection),
+}
+
+impl Binding {
+  pub fn device(self) -> InputDevice {
+    match self {
+      Self::Key(_) | Self::Mouse(_) => InputDevice::KeyboardMouse,
+      Self::GamepadButton(_) | Self::GamepadAxis(..) => InputDevice::Gamepad,
+    }
+  }
+
+  pub fn label(self) -> String {
+    match self {
+      Self::Key(key) => {
+        let name = format!("{key:?}");
+        name
+          .strip_prefix("Key")
+          .or_else(|| name.strip_prefix("Digit"))
+          .unwrap_or(&name)
+          .to_string()
+      }
+      Self::Mouse(button) => format!("Mouse {button:?}"),
+      Self::GamepadButton(button) => format!("{button:?}"),
+      Self::GamepadAxis(axis, AxisDirection::Positive) => format!("{axis:?}+"),
+      Self::GamepadAxis(axis, AxisDirection::Negative) => format!("{axis:?}-"),
+    }
+  }
+
+  /// How much the binding is pressed, between `0.0` and `1.0`.
+  fn value(
+    self,
+    keyboard: &ButtonInput<KeyCode>,
+    mouse: &ButtonInput<MouseButton>,
+    gamepads: &Query<&Gamepad>,
+  ) -> f32 {
+    match self {
+      Self::Key(key) => keyboard.pressed(key) as u8 as f32,
+      Self::Mouse(button) => mouse.pressed(button) as u8 as f32,
+      Self::GamepadButton(button) => gamepads
+        .iter()
+        .map(|gamepad| {
+          gamepad
+            .get(button)
+            .unwrap_or(gamepad.pressed(button) as u8 as f32)
+        })
+        .fold(0.0, f32::max),
+      Self::GamepadAxis(axis, direction) => gamepads
+        .iter()
+        .filter_map(|gamepad| gamepad.get(axis))
+        .map(|value| match direction {
+          AxisDirection::Positive => value.max(0.0),
+          AxisDirection::Negative => (-value).max(0.0),
+        })
+        .map(|value| {
+          ((value - STICK_DEADZONE) / (1.0 - STICK_DEADZONE)).max(0.0)
+        })
+        .fold(0.0, f32::max),
+    }
+  }
+}
+
+/// The bindings of every [`Action`], stored in [`Settings`].
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct InputMap {
+  bindings: HashMap<Action, Vec<Binding>>,
+}
+
+impl InputMap {
+  pub fn bindings(&self, action: Action) -> &[Binding] {
+    self.bindings.get(&action).map_or(&[], Vec::as_slice)
+  }
+
+  /// Replaces the bindings of an action on the device of `binding`.
+  pub fn rebind(&mut self, action: Action, binding: Binding) {
+    let bindings = self.bindings.entry(action).or_default();
+    bindings.retain(|bound| bound.device() != binding.device());
+    bindings.push(binding);
+  }
+}
+
+impl Default for InputMap {
+  fn default() -> Self {
+    use AxisDirection::*;
+    use Binding::{Key, Mouse};
+    let pad = Binding::GamepadButton;
+    let stick = Binding::GamepadAxis;
+
+    let bindings = all::<Action>()
+      .map(|action| {
+        let bindings = match action {
+          Action::MoveUp => vec![
+            Key(KeyCode::KeyW),
+            Key(KeyCode::ArrowUp),
+            pad(GamepadButton::DPadUp),
+            stick(GamepadAxis::LeftStickY, Positive),
+          ],
+          Action::MoveDown => vec![
+            Key(KeyCode::KeyS),
+            Key(KeyCode::ArrowDown),
+            pad(GamepadButton::DPadDown),
+            stick(GamepadAxis::LeftStickY, Negative),
+          ],
+          Action::MoveLeft => vec![
+            Key(KeyCode::KeyA),
+            Key(KeyCode::ArrowLeft),
+            pad(GamepadButton::DPadLeft),
+            stick(GamepadAxis::LeftStickX, Negative),
+          ],
+          Action::MoveRight => vec![
+            Key(KeyCode::KeyD),
+            Key(KeyCode::ArrowRight),
+            pad(GamepadButton::DPadRight),
+            stick(GamepadAxis::LeftStickX, Positive),
+          ],
+          Action::Attack => vec![
+            Key(KeyCode::KeyJ),
+            Mouse(MouseButton::Left),
+            pad(GamepadButton::West),
+          ],
+          Action::Dash => vec![
+            Key(KeyCode::Space),
+            Key(KeyCode::KeyK),
+            pad(GamepadButton::South),
+          ],
+          Action::Pause => {
+            vec![Key(KeyCode::Escape), pad(GamepadButton::Start)]
+          }
+          Action::Confirm => {
+            vec![Key(KeyCode::Enter), pad(GamepadButton::South)]
+          }
+          Action::Back => vec![
+            Key(KeyCode::Escape),
+            Key(KeyCode::Backspace),
+            pad(GamepadButton::East),
+          ],
+        };
+
+        (action, bindings)
+      })
+      .collect();
+
+    Self { bindings }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ActionValue {
+  value: f32,
+  pressed: bool,
+  just_pressed: bool,
+}
+
+/// The state of every [`Action`] this frame, read by gameplay and menus
+/// instead of the raw device inputs.
+#[derive(Resource, Default)]
+pub struct ActionState {
+  actions: HashMap<Action, ActionValue>,
+}
+
+impl ActionState {
+  fn get(&self, action: Action) -> ActionValue {
+    self.actions.get(&action).copied().unwrap_or_default()
+  }
+
+  pub fn pressed(&self, action: Action) -> bool {
+    self.get(action).pressed
+  }
+
+  pub fn just_pressed(&self, action: Action) -> bool {
+    self.get(action).just_pressed
+  }
+
+  /// How much the action is pressed, between `0.0` and `1.0`.
+  pub fn value(&self, action: Action) -> f32 {
+    self.get(action).value
+  }
+
+  /// The direction of the move actions, with a length up to `1.0` so
+  /// analog sticks can move slower than full speed.
+  pub fn move_direction(&self) -> Vec2 {
+    Vec2::new(
+      self.value(Action::MoveRight) - self.value(Action::MoveLeft),
+      self.value(Action::MoveUp) - self.value(Action::MoveDown),
+    )
+    .clamp_length_max(1.0)
+  }
+}
+
+fn update_action_state(
+  mut action_state: ResMut<ActionState>,
+  settings: Res<Settings>,
+  keyboard: Res<ButtonInput<KeyCode>>,
+  mouse: Res<ButtonInput<MouseButton>>,
+  gamepads: Query<&Gamepad>,
+) {
+  for action in all::<Action>() {
+    let value = settings
+      .controls
+      .bindings(action)
+      .iter()
+      .map(|binding| binding.value(&keyboard, &mouse, &gamepads))
+      .fold(0.0, f32::max);
+
+    let was_pressed = action_state.pressed(action);
+    let pressed = value >= PRESS_THRESHOLD;
+
+    action_state.actions.insert(
+      action,
+      ActionValue {
+        value,
+        pressed,
+        just_pressed: pressed && !was_pressed,
+      },
+    );
+  }
+}
+
+/// A message shown for a while when a controller connects or disconnects.
+#[derive(Component, Deref, DerefMut)]
+struct GamepadNotice(Timer);
+
+fn show_gamepad_notices(
+  mut commands: Commands,
+  mut connection_events: EventReader<GamepadConnectionEvent>,
+  names: Query<&Name>,
+  notices: Query<Entity, With<GamepadNotice>>,
+  ui_assets: Res<assets::UiAssets>,
+) {
+  let Some(event) = connection_events.read().last() else {
+    return;
+  };
+
+  let message = match &event.connection {
+    GamepadConnection::Connected { name, .. } => {
+      LocalizedText::new("gamepad.connected").with_arg("name", name)
+    }
+    GamepadConnection::Disconnected => match names.get(event.gamepad) {
+      Ok(name) => LocalizedText::new("gamepad.disconnected")
+        .with_arg("name", name.as_str()),
+      Err(_) => LocalizedText::new("gamepad.disconnected_unnamed"),
+    },
+  };
+
+  // Only the latest notice is relevant.
+  for entity in &notices {
+    commands.entity(entity).despawn_recursive();
+  }
+
+  commands.spawn((
+    Name::new("GamepadNotice"),
+    GamepadNotice(Timer::from_seconds(
+      GAMEPAD_NOTICE_DURATION,
+      TimerMode::Once,
+    )),
+    message,
+    TextFont {
+      font: ui_assets.font.clone(),
+      font_size: 18.,
+      ..default()
+    },
+    Node {
+      position_type: PositionType::Absolute,
+      bottom: Val::Px(20.),
+      left: Val::Px(20.),
+      ..default()
+    },
+    GlobalZIndex(i32::MAX),
+  ));
+}
+
+/// Notices are timed on real time, so they also go away while paused.
+fn despawn_gamepad_notices(
+  mut commands: Commands,
+  time: Res<Time<Real>>,
+  mut notices: Query<(Entity, &mut GamepadNotice)>,
+) {
+  for (entity, mut notice) in &mut notices {
+    if notice.tick(time.delta()).finished() {
+      commands.entity(entity).despawn_recursive();
+    }
+  }
+}
@@ -0,0 +1,193 @@
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::ecs::system::SystemParam;
+use bevy::ui::UiSystem;
+use bevy::utils::HashMap;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+  assets::LocaleAssets,
+  prelude::*,
+  settings::{Language, Settings},
+};
+
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .init_asset::<StringTable>()
+      .init_asset_loader::<StringTableLoader>()
+      .add_systems(
+        PostUpdate,
+        update_localized_text.before(UiSystem::Prepare),
+      );
+  }
+}
+
+/// The strings of one [`Language`], from a `.lang.ron` file under
+/// `assets/locales/`.
+#[derive(Asset, TypePath, Debug, Deref, Deserialize)]
+#[serde(transparent)]
+pub struct StringTable(HashMap<String, String>);
+
+/// Looks up strings in the table of the current [`Language`].
+#[derive(SystemParam)]
+pub struct Localization<'w> {
+  settings: Res<'w, Settings>,
+  locales: Option<Res<'w, LocaleAssets>>,
+  tables: Res<'w, Assets<StringTable>>,
+}
+
+impl Localization<'_> {
+  /// The string for a key, falling back to English and then to the key
+  /// itself when it is missing.
+  pub fn get(&self, key: &str) -> String {
+    self
+      .lookup(self.settings.language, key)
+      .or_else(|| self.lookup(Language::English, key))
+      .unwrap_or(key)
+      .to_string()
+  }
+
+  /// Like [`Localization::get`], replacing every `{name}` with its value.
+  pub fn format(&self, key: &str, args: &[(String, String)]) -> String {
+    args.iter().fold(self.get(key), |text, (name, value)| {
+      text.replace(&format!("{{{name}}}"), value)
+    })
+  }
+
+  fn lookup(&self, language: Language, key: &str) -> Option<&str> {
+    let handle = self.locales.as_ref()?.tables.get(&language)?;
+    self.tables.get(handle)?.get(key).map(String::as_str)
+  }
+
+  /// Whether strings may have changed since the last run of the system.
+  fn is_changed(&self) -> bool {
+    self.settings.is_changed()
+      || self
+        .locales
+        .as_ref()
+        .is_some_and(|locales| locales.is_added())
+  }
+}
+
+/// A [`Text`] showing the string for `key` in the current [`Language`].
+#[derive(Component, Debug, Clone)]
+#[require(Text)]
+pub struct LocalizedText {
+  pub key: String,
+  /// Values of the `{name}` placeholders of the string.
+  pub args: Vec<(String, String)>,
+}
+
+impl LocalizedText {
+  pub fn new(key: impl Into<String>) -> Self {
+    Self {
+      key: key.into(),
+      args: Vec::new(),
+    }
+  }
+
+  pub fn with_arg(
+    mut self,
+    name: impl Into<String>,
+    value: impl Into<String>,
+  ) -> Self {
+    self.set_arg(name, value);
+    self
+  }
+
+  /// Sets the value of a placeholder, replacing the previous one.
+  pub fn set_arg(&mut self, name: impl Into<String>, value: impl Into<String>) {
+    let name = name.into();
+    let value = value.into();
+
+    match self.args.iter_mut().find(|(arg, _)| *arg == name) {
+      Some((_, current)) => *current = value,
+      None => self.args.push((name, value)),
+    }
+  }
+}
+
+/// Re-renders texts whose key changed, or all of them when the language or
+/// the string tables change.
+fn update_localized_text(
+  localization: Localization,
+  mut table_events: EventReader<AssetEvent<StringTable>>,
+  mut texts: Query<(Ref<LocalizedText>, &mut Text)>,
+) {
+  let refresh = localization.is_changed() || table_events.read().count() > 0;
+
+  for (localized, mut text) in &mut texts {
+    if !refresh && !localized.is_changed() {
+      continue;
+    }
+
+    let value = localization.format(&localized.key, &localized.args);
+
+    if text.0 != value {
+      text.0 = value;
+    }
+  }
+}
+
+#[derive(Default)]
+pub struct StringTableLoader;
+
+#[derive(Debug, Error)]
+pub enum StringTableLoaderError {
+  #[error("could not read string table: {0}")]
+  Io(#[from] std::io::Error),
+  #[error("could not parse string table: {0}")]
+  Ron(#[from] ron::error::SpannedError),
+}
+
+impl AssetLoader for StringTableLoader {
+  type Asset = StringTable;
+  type Settings = ();
+  type Error = StringTableLoaderError;
+
+  async fn load(
+    &self,
+    reader: &mut dyn Reader,
+    _settings: &(),
+    _load_context: &mut LoadContext<'_>,
+  ) -> Result<Self::Asset, Self::Error> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).await?;
+
+    Ok(ron::de::from_bytes(&bytes)?)
+  }
+
+  fn extensions(&self) -> &[&str] {
+    &["lang.ron"]
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use bevy::utils::HashSet;
+
+  use super::*;
+
+  fn parse(contents: &str) -> StringTable {
+    ron::de::from_str(contents).expect("string table should parse")
+  }
+
+  #[test]
+  fn locales_have_the_same_keys() {
+    let english = parse(include_str!(
+      "../assets/locales/en.lang.ron"
+    ));
+    let italian = parse(include_str!(
+      "../assets/locales/it.lang.ron"
+    ));
+
+    let english_keys: HashSet<_> = english.keys().collect();
+    let italian_keys: HashSet<_> = italian.keys().collect();
+
+    assert_eq!(english_keys, italian_keys);
+  }
+}
@@ -1,16 +1,27 @@
 mod assets;
+mod audio;
+mod button_action;
 #[cfg(any(feature = "dev", feature = "web-dev"))]
 mod dev_tools;
+mod difficulty;
+// Browsers already pace frames to the display, and can't be blocked.
+#[cfg(not(target_arch = "wasm32"))]
+mod frame_limiter;
 mod game;
 mod game_over;
+mod input;
 mod loading;
+mod localization;
 mod main_menu;
+mod navigation;
 mod prelude;
+mod results;
 mod settings;
 mod state;
-mod utils;
+mod storage;
+mod victory;
+mod widgets;
 
-use bevy::window::{WindowMode, WindowResolution};
 use state::AppStatePlugin;
 
 use crate::prelude::*;
@@ -25,9 +36,7 @@ fn main() {
       fit_canvas_to_parent: true,
       canvas: Some("#game".to_string()),
       title: "Boss Rush 2025".to_string(),
-      #[cfg(not(any(feature = "web-dev", feature = "web")))]
-      resolution: WindowResolution::new(1280., 720.),
-      mode: WindowMode::Windowed,
+      // Size and mode come from the settings.
       present_mode: bevy::window::PresentMode::Fifo,
       ..Default::default()
     }),
@@ -45,8 +54,22 @@ fn main() {
 
   app.add_plugins(AppStatePlugin);
   app.add_plugins(settings::SettingsPlugin);
+  app.add_plugins(input::ActionsPlugin);
+  app.add_plugins(navigation::NavigationPlugin);
+  app.add_plugins(button_action::ButtonActionPlugin);
+  app.add_plugins(localization::LocalizationPlugin);
+  app.add_plugins(difficulty::DifficultyPlugin);
+  app.add_plugins(widgets::WidgetsPlugin::<
+    settings::Settings,
+  >::default());
+  app.add_plugins(widgets::WidgetsPlugin::<
+    difficulty::Difficulty,
+  >::default());
+  #[cfg(not(target_arch = "wasm32"))]
+  app.add_plugins(frame_limiter::FrameLimiterPlugin);
 
   app.add_plugins(assets::AssetsLoadingPlugin);
+  app.add_plugins(audio::GameAudioPlugin);
 
   app.add_plugins((
     loading::LoadscreenPlugin {
@@ -61,6 +84,9 @@ fn main() {
     game_over::GameOverPlugin {
       state: AppState::GameOver,
     },
+    victory::VictoryPlugin {
+      state: AppState::Victory,
+    },
   ));
 
   #[cfg(feature = "dev")]
@@ -0,0 +1,139 @@
+use assets::UiAssets;
+
+use crate::navigation::BackButton;
+use crate::prelude::*;
+use crate::settings::{Palette, Settings, GAME_SPEED_RANGE};
+use crate::widgets::{
+  spawn_selector, spawn_setting_row, spawn_slider, spawn_toggle, Selector,
+  Slider, Toggle,
+};
+
+const GAME_SPEED_STEP: f32 = 0.1;
+const SCREEN_SHAKE_STEP: f32 = 0.25;
+
+pub struct MainMenuAccessibilityPlugin;
+
+impl Plugin for MainMenuAccessibilityPlugin {
+  fn build(&self, app: &mut App) {
+    app.add_systems(
+      OnEnter(MainMenuState::Accessibility),
+      setup_main_menu_accessibility,
+    );
+  }
+}
+
+fn setup_main_menu_accessibility(mut commands: Commands, res: Res<UiAssets>) {
+  let text_font = TextFont {
+    font: res.font.clone(),
+    font_size: 32.0,
+    ..Default::default()
+  };
+
+  commands
+    .spawn((
+      Name::new("AccessibilityContainer"),
+      StateScoped(MainMenuState::Accessibility),
+      Node {
+        width: Val::Percent(100.),
+        height: Val::Percent(100.),
+        position_type: PositionType::Relative,
+        flex_direction: FlexDirection::Column,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        row_gap: Val::Px(16.),
+        ..Default::default()
+      },
+    ))
+    .with_children(|parent| {
+      parent.spawn((
+        LocalizedText::new("settings.accessibility"),
+        TextFont {
+          font_size: 40.,
+          ..text_font.clone()
+        },
+      ));
+
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "accessibility.game_speed",
+        |row| {
+          let (min, max) = GAME_SPEED_RANGE;
+
+          spawn_slider(
+            row,
+            &text_font,
+            Slider::new(
+              |settings: &Settings| settings.game_speed,
+              |settings, speed| settings.game_speed = speed,
+              GAME_SPEED_STEP,
+            )
+            .with_range(min, max),
+          );
+        },
+      );
+
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "accessibility.screen_shake",
+        |row| {
+          spawn_slider(
+            row,
+            &text_font,
+            Slider::new(
+              |settings: &Settings| settings.screen_shake,
+              |settings, scale| settings.screen_shake = scale,
+              SCREEN_SHAKE_STEP,
+            ),
+          );
+        },
+      );
+
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "accessibility.reduced_flashing",
+        |row| {
+          spawn_toggle(
+            row,
+            &text_font,
+            Toggle::new(
+              |settings: &Settings| settings.reduced_flashing,
+              |settings, enabled| settings.reduced_flashing = enabled,
+            ),
+          );
+        },
+      );
+
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "accessibility.palette",
+        |row| {
+          spawn_selector(
+            row,
+            &text_font,
+            Selector::new(
+              |settings: &Settings| settings.palette,
+              |settings, palette| settings.palette = palette,
+              |palette: Palette| palette.text_key().to_string(),
+            ),
+          );
+        },
+      );
+
+      parent
+        .spawn((
+          Button,
+          BackButton,
+          ButtonAction::set_state(MainMenuState::Settings),
+        ))
+        .with_children(|parent| {
+          parent.spawn((
+            LocalizedText::new("common.back"),
+            text_font.clone(),
+          ));
+        });
+    });
+}
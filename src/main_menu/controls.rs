@@ -0,0 +1,284 @@
+use assets::UiAssets;
+use bevy::input::gamepad::GamepadInput;
+use enum_iterator::all;
+
+use crate::input::{AxisDirection, Binding, InputDevice, InputMap};
+use crate::localization::Localization;
+use crate::navigation::{BackButton, NavigationLock};
+use crate::{prelude::*, settings};
+
+/// How far a stick has to be pushed to be picked as a binding.
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// A button that rebinds an action on one kind of device.
+#[derive(Component)]
+struct RebindButton {
+  action: Action,
+  device: InputDevice,
+}
+
+/// Starts waiting for the input to bind to an action.
+#[derive(Event, Clone)]
+struct StartRebinding {
+  action: Action,
+  device: InputDevice,
+}
+
+/// Present while waiting for the input to bind to an action.
+#[derive(Resource)]
+struct Rebinding {
+  action: Action,
+  device: InputDevice,
+}
+
+pub struct MainMenuControlsPlugin;
+
+impl Plugin for MainMenuControlsPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .add_event::<StartRebinding>()
+      .add_systems(
+        OnEnter(MainMenuState::Controls),
+        setup_main_menu_controls,
+      )
+      .add_systems(
+        OnExit(MainMenuState::Controls),
+        |mut commands: Commands| {
+          commands.remove_resource::<Rebinding>();
+          commands.remove_resource::<NavigationLock>();
+        },
+      )
+      .add_systems(
+        Update,
+        (
+          start_rebinding,
+          capture_binding.run_if(resource_exists::<Rebinding>),
+          update_binding_labels,
+        )
+          .chain()
+          .run_if(in_state(MainMenuState::Controls)),
+      );
+  }
+}
+
+fn setup_main_menu_controls(mut commands: Commands, res: Res<UiAssets>) {
+  let text_font = |font_size: f32| TextFont {
+    font: res.font.clone(),
+    font_size,
+    ..Default::default()
+  };
+
+  commands
+    .spawn((
+      Name::new("ControlsContainer"),
+      StateScoped(MainMenuState::Controls),
+      Node {
+        width: Val::Percent(100.),
+        height: Val::Percent(100.),
+        position_type: PositionType::Relative,
+        flex_direction: FlexDirection::Column,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        row_gap: Val::Px(12.),
+        ..Default::default()
+      },
+    ))
+    .with_children(|parent| {
+      parent.spawn((
+        LocalizedText::new("settings.controls"),
+        text_font(40.),
+      ));
+
+      for action in all::<Action>() {
+        parent
+          .spawn(Node {
+            column_gap: Val::Px(24.),
+            align_items: AlignItems::Center,
+            ..Default::default()
+          })
+          .with_children(|row| {
+            row.spawn((
+              LocalizedText::new(action.text_key()),
+              text_font(24.),
+              Node {
+                width: Val::Px(180.),
+                ..Default::default()
+              },
+            ));
+
+            for device in [InputDevice::KeyboardMouse, InputDevice::Gamepad] {
+              row
+                .spawn((
+                  Button,
+                  RebindButton { action, device },
+                  ButtonAction::send_event(StartRebinding { action, device }),
+                  Node {
+                    width: Val::Px(280.),
+                    ..Default::default()
+                  },
+                ))
+                .with_children(|button| {
+                  button.spawn((Text::default(), text_font(24.)));
+                });
+            }
+          });
+      }
+
+      parent
+        .spawn((
+          Button,
+          ButtonAction::run_system(reset_controls),
+        ))
+        .with_children(|button| {
+          button.spawn((
+            LocalizedText::new("controls.reset"),
+            text_font(32.),
+          ));
+        });
+
+      parent
+        .spawn((
+          Button,
+          BackButton,
+          ButtonAction::set_state(MainMenuState::Settings),
+        ))
+        .with_children(|button| {
+          button.spawn((
+            LocalizedText::new("common.back"),
+            text_font(32.),
+          ));
+        });
+    });
+}
+
+fn start_rebinding(
+  mut commands: Commands,
+  mut start_rebinding_events: EventReader<StartRebinding>,
+) {
+  for event in start_rebinding_events.read() {
+    commands.insert_resource(Rebinding {
+      action: event.action,
+      device: event.device,
+    });
+    commands.insert_resource(NavigationLock);
+  }
+}
+
+/// Binds the next input of the right device to the action being rebound.
+/// Escape cancels the rebinding of a gamepad input.
+fn capture_binding(
+  mut commands: Commands,
+  rebinding: Res<Rebinding>,
+  mut settings: ResMut<settings::Settings>,
+  keyboard: Res<ButtonInput<KeyCode>>,
+  mouse: Res<ButtonInput<MouseButton>>,
+  gamepads: Query<&Gamepad>,
+) {
+  // The click that started the rebinding is not a binding.
+  if rebinding.is_added() {
+    return;
+  }
+
+  let binding = match rebinding.device {
+    InputDevice::KeyboardMouse => keyboard
+      .get_just_pressed()
+      .next()
+      .map(|key| Binding::Key(*key))
+      .or_else(|| {
+        mouse
+          .get_just_pressed()
+          .next()
+          .map(|button| Binding::Mouse(*button))
+      }),
+    InputDevice::Gamepad => {
+      if keyboard.just_pressed(KeyCode::Escape) {
+        commands.remove_resource::<Rebinding>();
+        commands.remove_resource::<NavigationLock>();
+        return;
+      }
+
+      gamepads.iter().find_map(gamepad_binding)
+    }
+  };
+
+  if let Some(binding) = binding {
+    settings.controls.rebind(rebinding.action, binding);
+    commands.remove_resource::<Rebinding>();
+    commands.remove_resource::<NavigationLock>();
+  }
+}
+
+/// The button just pressed or the stick pushed on a gamepad.
+fn gamepad_binding(gamepad: &Gamepad) -> Option<Binding> {
+  if let Some(button) = gamepad.get_just_pressed().next() {
+    return Some(Binding::GamepadButton(*button));
+  }
+
+  gamepad.get_analog_axes().find_map(|input| {
+    let GamepadInput::Axis(axis) = *input else {
+      return None;
+    };
+
+    let value = gamepad.get(axis)?;
+
+    if value >= STICK_THRESHOLD {
+      Some(Binding::GamepadAxis(
+        axis,
+        AxisDirection::Positive,
+      ))
+    } else if value <= -STICK_THRESHOLD {
+      Some(Binding::GamepadAxis(
+        axis,
+        AxisDirection::Negative,
+      ))
+    } else {
+      None
+    }
+  })
+}
+
+fn reset_controls(mut settings: ResMut<settings::Settings>) {
+  settings.controls = InputMap::default();
+}
+
+fn update_binding_labels(
+  settings: Res<settings::Settings>,
+  localization: Localization,
+  rebinding: Option<Res<Rebinding>>,
+  buttons: Query<(&RebindButton, &Children)>,
+  mut texts: Query<&mut Text>,
+) {
+  for (button, children) in &buttons {
+    let listening = rebinding.as_ref().is_some_and(|rebinding| {
+      rebinding.action == button.action && rebinding.device == button.device
+    });
+
+    let label = if listening {
+      match button.device {
+        InputDevice::KeyboardMouse => localization.get("controls.press_key"),
+        InputDevice::Gamepad => localization.get("controls.press_button"),
+      }
+    } else {
+      let bindings = settings
+        .controls
+        .bindings(button.action)
+        .iter()
+        .filter(|binding| binding.device() == button.device)
+        .map(|binding| binding.label())
+        .collect::<Vec<_>>();
+
+      match bindings.is_empty() {
+        true => "-".to_string(),
+        false => bindings.join(" / "),
+      }
+    };
+
+    for child in children {
+      if let Ok(mut text) = texts.get_mut(*child) {
+        if text.0 != label {
+          text.0 = label.clone();
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,175 @@
+use assets::UiAssets;
+
+use crate::difficulty::{Difficulty, DifficultyLevel};
+use crate::navigation::BackButton;
+use crate::prelude::*;
+use crate::widgets::{
+  spawn_selector, spawn_setting_row, spawn_slider, spawn_toggle, Selector,
+  Slider, Toggle,
+};
+
+const DAMAGE_TAKEN_STEP: f32 = 0.25;
+const DAMAGE_TAKEN_RANGE: (f32, f32) = (0.25, 1.0);
+
+pub struct MainMenuDifficultyPlugin;
+
+impl Plugin for MainMenuDifficultyPlugin {
+  fn build(&self, app: &mut App) {
+    app.add_systems(
+      OnEnter(MainMenuState::Difficulty),
+      setup_main_menu_difficulty,
+    );
+  }
+}
+
+fn setup_main_menu_difficulty(mut commands: Commands, res: Res<UiAssets>) {
+  let text_font = TextFont {
+    font: res.font.clone(),
+    font_size: 32.0,
+    ..Default::default()
+  };
+
+  commands
+    .spawn((
+      Name::new("DifficultyContainer"),
+      StateScoped(MainMenuState::Difficulty),
+      Node {
+        width: Val::Percent(100.),
+        height: Val::Percent(100.),
+        position_type: PositionType::Relative,
+        flex_direction: FlexDirection::Column,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        row_gap: Val::Px(16.),
+        ..Default::default()
+      },
+    ))
+    .with_children(|parent| {
+      parent.spawn((
+        LocalizedText::new("difficulty.title"),
+        TextFont {
+          font_size: 40.,
+          ..text_font.clone()
+        },
+      ));
+
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "difficulty.level",
+        |row| {
+          spawn_selector(
+            row,
+            &text_font,
+            Selector::new(
+              |difficulty: &Difficulty| difficulty.level,
+              |difficulty, level| difficulty.level = level,
+              |level: DifficultyLevel| level.text_key().to_string(),
+            ),
+          );
+        },
+      );
+
+      parent.spawn((
+        LocalizedText::new("assists.title"),
+        text_font.clone(),
+      ));
+
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "assists.damage_taken",
+        |row| {
+          let (min, max) = DAMAGE_TAKEN_RANGE;
+
+          spawn_slider(
+            row,
+            &text_font,
+            Slider::new(
+              |difficulty: &Difficulty| difficulty.assists.damage_taken,
+              |difficulty, damage| difficulty.assists.damage_taken = damage,
+              DAMAGE_TAKEN_STEP,
+            )
+            .with_range(min, max),
+          );
+        },
+      );
+
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "assists.extra_invulnerability",
+        |row| {
+          spawn_toggle(
+            row,
+            &text_font,
+            Toggle::new(
+              |difficulty: &Difficulty| {
+                difficulty.assists.extra_invulnerability
+              },
+              |difficulty, enabled| {
+                difficulty.assists.extra_invulnerability = enabled
+              },
+            ),
+          );
+        },
+      );
+
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "assists.infinite_dash",
+        |row| {
+          spawn_toggle(
+            row,
+            &text_font,
+            Toggle::new(
+              |difficulty: &Difficulty| difficulty.assists.infinite_dash,
+              |difficulty, enabled| difficulty.assists.infinite_dash = enabled,
+            ),
+          );
+        },
+      );
+
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "assists.no_fail",
+        |row| {
+          spawn_toggle(
+            row,
+            &text_font,
+            Toggle::new(
+              |difficulty: &Difficulty| difficulty.assists.no_fail,
+              |difficulty, enabled| difficulty.assists.no_fail = enabled,
+            ),
+          );
+        },
+      );
+
+      parent
+        .spawn((
+          Button,
+          ButtonAction::set_state(AppState::InGame),
+        ))
+        .with_children(|parent| {
+          parent.spawn((
+            LocalizedText::new("difficulty.start"),
+            text_font.clone(),
+          ));
+        });
+
+      parent
+        .spawn((
+          Button,
+          BackButton,
+          ButtonAction::set_state(MainMenuState::MainScreen),
+        ))
+        .with_children(|parent| {
+          parent.spawn((
+            LocalizedText::new("common.back"),
+            text_font.clone(),
+          ));
+        });
+    });
+}
@@ -1,13 +1,10 @@
+mod accessibility;
+mod controls;
+mod difficulty;
 mod settings;
 
 use crate::prelude::*;
 
-#[derive(Component)]
-struct PlayButton;
-
-#[derive(Component)]
-struct SettingsButton;
-
 pub struct MainMenuPlugin<S: States> {
   pub state: S,
 }
@@ -15,7 +12,12 @@ pub struct MainMenuPlugin<S: States> {
 impl<S: States> Plugin for MainMenuPlugin<S> {
   fn build(&self, app: &mut App) {
     app
-      .add_plugins(settings::MainMenuSettingsPlugin)
+      .add_plugins((
+        settings::MainMenuSettingsPlugin,
+        controls::MainMenuControlsPlugin,
+        accessibility::MainMenuAccessibilityPlugin,
+        difficulty::MainMenuDifficultyPlugin,
+      ))
       .add_systems(
         OnEnter(self.state.clone()),
         setup_main_menu_camera,
@@ -23,11 +25,6 @@ impl<S: States> Plugin for MainMenuPlugin<S> {
       .add_systems(
         OnEnter(MainMenuState::MainScreen),
         spawn_main_menu_ui,
-      )
-      .add_systems(
-        Update,
-        (start_game, go_to_settings)
-          .run_if(in_state(MainMenuState::MainScreen)),
       );
   }
 }
@@ -59,13 +56,12 @@ fn spawn_main_menu_ui(mut commands: Commands, res: Res<assets::UiAssets>) {
 
   let play_button = commands
     .spawn((
-      PlayButton,
       Button,
-      StateOnPress::from(AppState::InGame),
+      ButtonAction::set_state(MainMenuState::Difficulty),
     ))
     .with_children(|parent| {
       parent.spawn((
-        Text::new("Play"),
+        LocalizedText::new("menu.play"),
         TextFont {
           font: res.font.clone(),
           font_size: 32.,
@@ -77,13 +73,12 @@ fn spawn_main_menu_ui(mut commands: Commands, res: Res<assets::UiAssets>) {
 
   let settings_button = commands
     .spawn((
-      SettingsButton,
       Button,
-      StateOnPress::from(MainMenuState::Settings),
+      ButtonAction::set_state(MainMenuState::Settings),
     ))
     .with_children(|parent| {
       parent.spawn((
-        Text::new("Settings"),
+        LocalizedText::new("menu.settings"),
         TextFont {
           font: res.font.clone(),
           font_size: 32.,
@@ -97,42 +92,3 @@ fn spawn_main_menu_ui(mut commands: Commands, res: Res<assets::UiAssets>) {
     .entity(container)
     .add_children(&[play_button, settings_button]);
 }
-
-fn start_game(
-  mut play_interaction_query: Query<
-    (&Interaction, &StateOnPress<AppState>),
-    (
-      Changed<Interaction>,
-      With<Button>,
-      With<PlayButton>,
-    ),
-  >,
-  mut next_app_state: ResMut<NextState<AppState>>,
-) {
-  for (interaction, state) in &mut play_interaction_query {
-    if interaction == &Interaction::Pressed {
-      next_app_state.set(state.action);
-    }
-  }
-}
-
-fn go_to_settings(
-  mut settings_interaction_query: Query<
-    (
-      &Interaction,
-      &StateOnPress<MainMenuState>,
-    ),
-    (
-      Changed<Interaction>,
-      With<Button>,
-      With<SettingsButton>,
-    ),
-  >,
-  mut next_main_menu_state: ResMut<NextState<MainMenuState>>,
-) {
-  for (interaction, state) in &mut settings_interaction_query {
-    if interaction == &Interaction::Pressed {
-      next_main_menu_state.set(state.action);
-    }
-  }
-}
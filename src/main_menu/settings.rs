@@ -1,35 +1,38 @@
 use assets::UiAssets;
 
-use crate::{prelude::*, settings};
+use crate::navigation::BackButton;
+use crate::prelude::*;
+use crate::settings::{
+  DisplayMode, Language, Quality, Settings, TimingMethod, UI_SCALE_RANGE,
+};
+use crate::widgets::{
+  spawn_selector, spawn_setting_row, spawn_slider, spawn_toggle, Selector,
+  Slider, Toggle,
+};
 
-#[derive(Component)]
-struct VSyncButton;
-
-#[derive(Component)]
-struct BackButton;
+/// How much the volume sliders move with the left and right actions.
+const VOLUME_STEP: f32 = 0.1;
+const UI_SCALE_STEP: f32 = 0.25;
 
 pub struct MainMenuSettingsPlugin;
 
 impl Plugin for MainMenuSettingsPlugin {
   fn build(&self, app: &mut App) {
-    app
-      .add_systems(
-        OnEnter(MainMenuState::Settings),
-        setup_main_menu_settings,
-      )
-      .add_systems(
-        Update,
-        (go_back, toggle_vsync).run_if(in_state(MainMenuState::Settings)),
-      );
+    app.add_systems(
+      OnEnter(MainMenuState::Settings),
+      setup_main_menu_settings,
+    );
   }
 }
 
-fn setup_main_menu_settings(
-  mut commands: Commands,
-  res: Res<UiAssets>,
-  settings: Res<settings::Settings>,
-) {
-  let settings_container = commands
+fn setup_main_menu_settings(mut commands: Commands, res: Res<UiAssets>) {
+  let text_font = TextFont {
+    font: res.font.clone(),
+    font_size: 32.0,
+    ..Default::default()
+  };
+
+  commands
     .spawn((
       Name::new("SettingsContainer"),
       StateScoped(MainMenuState::Settings),
@@ -40,89 +43,222 @@ fn setup_main_menu_settings(
         flex_direction: FlexDirection::Column,
         justify_content: JustifyContent::Center,
         align_items: AlignItems::Center,
-        row_gap: Val::Px(32.),
+        row_gap: Val::Px(16.),
         ..Default::default()
       },
     ))
-    .id();
+    .with_children(|parent| {
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "settings.music",
+        |row| {
+          spawn_slider(
+            row,
+            &text_font,
+            Slider::new(
+              |settings: &Settings| settings.music_level,
+              |settings, level| settings.music_level = level,
+              VOLUME_STEP,
+            ),
+          );
+        },
+      );
 
-  let text = match settings.vsync_enabled {
-    true => "VSync Enabled",
-    false => "VSync Disabled",
-  };
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "settings.sound",
+        |row| {
+          spawn_slider(
+            row,
+            &text_font,
+            Slider::new(
+              |settings: &Settings| settings.sound_level,
+              |settings, level| settings.sound_level = level,
+              VOLUME_STEP,
+            ),
+          );
+        },
+      );
 
-  let vsync_toggle = commands
-    .spawn((Button, VSyncButton))
-    .with_children(|parent| {
-      parent.spawn((
-        Text::new(text),
-        VSyncButton,
-        TextFont {
-          font: res.font.clone(),
-          font_size: 32.0,
-          ..Default::default()
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "settings.display_mode",
+        |row| {
+          spawn_selector(
+            row,
+            &text_font,
+            Selector::new(
+              |settings: &Settings| settings.display_mode,
+              |settings, mode| settings.display_mode = mode,
+              |mode: DisplayMode| mode.text_key().to_string(),
+            ),
+          );
         },
-      ));
-    })
-    .id();
+      );
 
-  let back_button = commands
-    .spawn((
-      Button,
-      BackButton,
-      StateOnPress::from(MainMenuState::MainScreen),
-    ))
-    .with_children(|parent| {
-      parent.spawn((
-        Text::new("Back"),
-        TextFont {
-          font: res.font.clone(),
-          font_size: 32.0,
-          ..Default::default()
+      // On the web the canvas follows the size of the page.
+      #[cfg(not(target_arch = "wasm32"))]
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "settings.resolution",
+        |row| {
+          spawn_selector(
+            row,
+            &text_font,
+            Selector::new(
+              |settings: &Settings| settings.resolution,
+              |settings, resolution| settings.resolution = resolution,
+              crate::settings::Resolution::text_key,
+            ),
+          );
         },
-      ));
-    })
-    .id();
+      );
 
-  commands
-    .entity(settings_container)
-    .add_children(&[vsync_toggle, back_button]);
-}
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "settings.ui_scale",
+        |row| {
+          let (min, max) = UI_SCALE_RANGE;
 
-fn toggle_vsync(
-  mut settings: ResMut<settings::Settings>,
-  vsync_button_query: Query<
-    &Interaction,
-    (Changed<Interaction>, With<VSyncButton>),
-  >,
-  mut vsync_button_text: Query<&mut Text, With<VSyncButton>>,
-) {
-  for interaction in &vsync_button_query {
-    if interaction == &Interaction::Pressed {
-      settings.vsync_enabled = !settings.vsync_enabled;
-      let mut text = vsync_button_text.single_mut();
-
-      text.0 = match settings.vsync_enabled {
-        true => "VSync Enabled".to_string(),
-        false => "VSync Disabled".to_string(),
-      };
-    }
-  }
-}
+          spawn_slider(
+            row,
+            &text_font,
+            Slider::new(
+              |settings: &Settings| settings.ui_scale,
+              |settings, scale| settings.ui_scale = scale,
+              UI_SCALE_STEP,
+            )
+            .with_range(min, max),
+          );
+        },
+      );
 
-fn go_back(
-  go_back_query: Query<
-    (
-      &Interaction,
-      &StateOnPress<MainMenuState>,
-    ),
-    Changed<Interaction>,
-  >,
-  mut next_state: ResMut<NextState<MainMenuState>>,
-) {
-  for (interaction, state) in &go_back_query {
-    if interaction == &Interaction::Pressed {
-      next_state.set(state.action);
-    }
-  }
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "settings.vsync",
+        |row| {
+          spawn_toggle(
+            row,
+            &text_font,
+            Toggle::new(
+              |settings: &Settings| settings.vsync_enabled,
+              |settings, enabled| settings.vsync_enabled = enabled,
+            ),
+          );
+        },
+      );
+
+      // Browsers pace frames on their own.
+      #[cfg(not(target_arch = "wasm32"))]
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "settings.frame_cap",
+        |row| {
+          spawn_selector(
+            row,
+            &text_font,
+            Selector::new(
+              |settings: &Settings| settings.frame_cap,
+              |settings, frame_cap| settings.frame_cap = frame_cap,
+              crate::settings::FrameCap::text_key,
+            ),
+          );
+        },
+      );
+
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "settings.quality",
+        |row| {
+          spawn_selector(
+            row,
+            &text_font,
+            Selector::new(
+              |settings: &Settings| settings.quality,
+              |settings, quality| settings.quality = quality,
+              |quality: Quality| quality.text_key().to_string(),
+            ),
+          );
+        },
+      );
+
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "settings.timing",
+        |row| {
+          spawn_selector(
+            row,
+            &text_font,
+            Selector::new(
+              |settings: &Settings| settings.timing,
+              |settings, timing| settings.timing = timing,
+              |timing: TimingMethod| timing.text_key().to_string(),
+            ),
+          );
+        },
+      );
+
+      spawn_setting_row(
+        parent,
+        &text_font,
+        "settings.language",
+        |row| {
+          spawn_selector(
+            row,
+            &text_font,
+            Selector::new(
+              |settings: &Settings| settings.language,
+              |settings, language| settings.language = language,
+              |language: Language| format!("language.{}", language.code()),
+            ),
+          );
+        },
+      );
+
+      parent
+        .spawn((
+          Button,
+          ButtonAction::set_state(MainMenuState::Controls),
+        ))
+        .with_children(|parent| {
+          parent.spawn((
+            LocalizedText::new("settings.controls"),
+            text_font.clone(),
+          ));
+        });
+
+      parent
+        .spawn((
+          Button,
+          ButtonAction::set_state(MainMenuState::Accessibility),
+        ))
+        .with_children(|parent| {
+          parent.spawn((
+            LocalizedText::new("settings.accessibility"),
+            text_font.clone(),
+          ));
+        });
+
+      parent
+        .spawn((
+          Button,
+          BackButton,
+          ButtonAction::set_state(MainMenuState::MainScreen),
+        ))
+        .with_children(|parent| {
+          parent.spawn((
+            LocalizedText::new("common.back"),
+            text_font.clone(),
+          ));
+        });
+    });
 }
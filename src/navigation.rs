@@ -0,0 +1,217 @@
+use bevy::ui::UiSystem;
+
+use crate::{input::ActionSystem, prelude::*};
+
+const FOCUS_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+
+pub struct NavigationPlugin;
+
+impl Plugin for NavigationPlugin {
+  fn build(&self, app: &mut App) {
+    app.init_resource::<Activated>().add_systems(
+      PreUpdate,
+      (
+        release_activated,
+        (
+          focus_hovered,
+          navigate_focus,
+          activate_focused,
+          activate_back,
+        )
+          .chain()
+          .run_if(not(resource_exists::<NavigationLock>)),
+      )
+        .chain()
+        .after(UiSystem::Focus)
+        .after(ActionSystem),
+    );
+  }
+}
+
+/// The button selected with the keyboard or a gamepad.
+#[derive(Component)]
+pub struct Focused;
+
+/// The button pressed by the back action on the screen it belongs to.
+#[derive(Component)]
+pub struct BackButton;
+
+/// A focusable widget that uses the left and right actions itself, so they
+/// don't move the focus away from it.
+#[derive(Component, Default)]
+pub struct Adjustable;
+
+/// While present, navigation ignores input, e.g. while a menu is waiting
+/// for an input to rebind.
+#[derive(Resource)]
+pub struct NavigationLock;
+
+/// Buttons pressed through navigation on the previous frame.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct Activated(Vec<Entity>);
+
+fn focus(commands: &mut Commands, entity: Entity) {
+  commands.entity(entity).insert((
+    Focused,
+    Outline::new(Val::Px(2.), Val::Px(6.), FOCUS_COLOR),
+  ));
+}
+
+fn unfocus(commands: &mut Commands, entity: Entity) {
+  commands.entity(entity).remove::<(Focused, Outline)>();
+}
+
+/// Moves the focus to the closest visible button in the direction pressed.
+fn navigate_focus(
+  mut commands: Commands,
+  actions: Res<ActionState>,
+  buttons: Query<
+    (
+      Entity,
+      &GlobalTransform,
+      &ViewVisibility,
+    ),
+    With<Button>,
+  >,
+  focused: Query<(Entity, Has<Adjustable>), With<Focused>>,
+) {
+  // UI positions grow downwards.
+  let Some(direction) = [
+    (Action::MoveUp, Vec2::NEG_Y),
+    (Action::MoveDown, Vec2::Y),
+    (Action::MoveLeft, Vec2::NEG_X),
+    (Action::MoveRight, Vec2::X),
+  ]
+  .into_iter()
+  .find(|(action, _)| actions.just_pressed(*action))
+  .map(|(_, direction)| direction) else {
+    return;
+  };
+
+  let focused_adjustable =
+    focused.get_single().is_ok_and(|(_, adjustable)| adjustable);
+
+  if focused_adjustable && direction.y == 0.0 {
+    return;
+  }
+
+  let buttons = buttons
+    .iter()
+    .filter(|(_, _, visibility)| visibility.get())
+    .map(|(entity, transform, _)| {
+      (
+        entity,
+        transform.translation().truncate(),
+      )
+    })
+    .collect::<Vec<_>>();
+
+  let current = focused.get_single().ok().and_then(|(current, _)| {
+    buttons.iter().find(|(entity, _)| *entity == current)
+  });
+
+  let next = match current {
+    // Prefers buttons aligned with the direction over closer ones on the side.
+    Some(&(current, position)) => buttons
+      .iter()
+      .filter(|(entity, _)| *entity != current)
+      .filter_map(|&(entity, other)| {
+        let offset = other - position;
+        let along = offset.dot(direction);
+        let across = offset.perp_dot(direction).abs();
+
+        (along > 0.0).then_some((entity, along + across * 2.0))
+      })
+      .min_by(|(_, a), (_, b)| a.total_cmp(b))
+      .map(|(entity, _)| entity),
+    // Starts from the first button in reading order.
+    None => buttons
+      .iter()
+      .min_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)))
+      .map(|(entity, _)| *entity),
+  };
+
+  let Some(next) = next else {
+    return;
+  };
+
+  for (entity, _) in &focused {
+    unfocus(&mut commands, entity);
+  }
+
+  focus(&mut commands, next);
+}
+
+/// Moves the focus to the button under the mouse, so only one button is
+/// highlighted at a time.
+fn focus_hovered(
+  mut commands: Commands,
+  buttons: Query<(Entity, &Interaction), (Changed<Interaction>, With<Button>)>,
+  focused: Query<Entity, With<Focused>>,
+) {
+  for (entity, interaction) in &buttons {
+    if *interaction != Interaction::Hovered || focused.contains(entity) {
+      continue;
+    }
+
+    for entity in &focused {
+      unfocus(&mut commands, entity);
+    }
+
+    focus(&mut commands, entity);
+  }
+}
+
+/// Presses the focused button for one frame on the confirm action, so the
+/// usual [`Interaction`] handlers run.
+fn activate_focused(
+  actions: Res<ActionState>,
+  mut focused: Query<(Entity, &mut Interaction), With<Focused>>,
+  mut activated: ResMut<Activated>,
+) {
+  if !actions.just_pressed(Action::Confirm) {
+    return;
+  }
+
+  for (entity, mut interaction) in &mut focused {
+    *interaction = Interaction::Pressed;
+    activated.push(entity);
+  }
+}
+
+/// Presses the visible [`BackButton`] on the back action.
+fn activate_back(
+  actions: Res<ActionState>,
+  mut buttons: Query<
+    (
+      Entity,
+      &mut Interaction,
+      &ViewVisibility,
+    ),
+    With<BackButton>,
+  >,
+  mut activated: ResMut<Activated>,
+) {
+  if !actions.just_pressed(Action::Back) {
+    return;
+  }
+
+  if let Some((entity, mut interaction, _)) = buttons
+    .iter_mut()
+    .find(|(_, _, visibility)| visibility.get())
+  {
+    *interaction = Interaction::Pressed;
+    activated.push(entity);
+  }
+}
+
+fn release_activated(
+  mut interactions: Query<&mut Interaction>,
+  mut activated: ResMut<Activated>,
+) {
+  for entity in activated.drain(..) {
+    if let Ok(mut interaction) = interactions.get_mut(entity) {
+      *interaction = Interaction::None;
+    }
+  }
+}
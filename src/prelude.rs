@@ -1,7 +1,9 @@
 pub use crate::state::*;
 pub use bevy::prelude::*;
 
-pub use crate::utils::StateOnPress;
+pub use crate::button_action::ButtonAction;
+pub use crate::input::{Action, ActionState};
+pub use crate::localization::LocalizedText;
 
 pub mod assets {
   pub use crate::assets::UiAssets;
@@ -0,0 +1,150 @@
+use crate::difficulty::Difficulty;
+use crate::game::{format_time, FightScore};
+use crate::prelude::*;
+
+/// How the last run went, kept after it ends for the victory and game over
+/// screens.
+#[derive(Resource, Debug, Clone)]
+pub struct RunResult {
+  /// The difficulty the run was played at.
+  pub difficulty: Difficulty,
+  /// The [`game_speed`](crate::settings::Settings::game_speed) the run was
+  /// played at.
+  pub game_speed: f32,
+  pub bosses_defeated: usize,
+  pub boss_count: usize,
+  /// Final time on the clock in use, for complete runs only.
+  pub time: Option<f32>,
+  /// Whether the run set a new personal best on that clock.
+  pub personal_best: bool,
+  /// Scores of the fights won, in order.
+  pub fights: Vec<FightScore>,
+}
+
+impl RunResult {
+  pub fn new(
+    difficulty: Difficulty,
+    game_speed: f32,
+    boss_count: usize,
+  ) -> Self {
+    Self {
+      difficulty,
+      game_speed,
+      bosses_defeated: 0,
+      boss_count,
+      time: None,
+      personal_best: false,
+      fights: Vec::new(),
+    }
+  }
+
+  /// Whether the run was played at a reduced game speed.
+  pub fn slowed(&self) -> bool {
+    self.game_speed != 1.0
+  }
+
+  /// Whether the run can set personal bests, without assists and at full
+  /// game speed.
+  pub fn ranked(&self) -> bool {
+    !self.difficulty.assists.any() && !self.slowed()
+  }
+
+  /// The sum of the scores of the fights won.
+  pub fn score(&self) -> u32 {
+    self.fights.iter().map(|fight| fight.score).sum()
+  }
+}
+
+/// Spawns the lines describing a [`RunResult`] as a column.
+pub fn spawn_run_result(
+  commands: &mut Commands,
+  font: &TextFont,
+  result: &RunResult,
+) -> Entity {
+  commands
+    .spawn((
+      Name::new("RunResult"),
+      Node {
+        flex_direction: FlexDirection::Column,
+        align_items: AlignItems::Center,
+        row_gap: Val::Px(8.),
+        ..Default::default()
+      },
+    ))
+    .with_children(|parent| {
+      parent
+        .spawn(Node {
+          column_gap: Val::Px(12.),
+          ..Default::default()
+        })
+        .with_children(|row| {
+          row.spawn((
+            LocalizedText::new("results.difficulty"),
+            font.clone(),
+          ));
+          row.spawn((
+            LocalizedText::new(result.difficulty.level.text_key()),
+            font.clone(),
+          ));
+        });
+
+      if result.difficulty.assists.any() {
+        parent.spawn((
+          LocalizedText::new("results.assists"),
+          font.clone(),
+        ));
+      }
+
+      if result.slowed() {
+        parent.spawn((
+          LocalizedText::new("results.slowed").with_arg(
+            "speed",
+            format!("{:.0}%", result.game_speed * 100.),
+          ),
+          font.clone(),
+        ));
+      }
+
+      parent.spawn((
+        LocalizedText::new("results.bosses_defeated")
+          .with_arg(
+            "defeated",
+            result.bosses_defeated.to_string(),
+          )
+          .with_arg("total", result.boss_count.to_string()),
+        font.clone(),
+      ));
+
+      if let Some(time) = result.time {
+        parent.spawn((
+          LocalizedText::new("results.time")
+            .with_arg("time", format_time(time, false)),
+          font.clone(),
+        ));
+      }
+
+      if result.personal_best {
+        parent.spawn((
+          LocalizedText::new("results.personal_best"),
+          font.clone(),
+        ));
+      }
+
+      for fight in &result.fights {
+        parent.spawn((
+          LocalizedText::new("results.fight")
+            .with_arg("boss", &fight.boss)
+            .with_arg("score", fight.score.to_string())
+            .with_arg("rank", fight.rank.label()),
+          font.clone(),
+        ));
+      }
+
+      parent.spawn((
+        LocalizedText::new("results.score")
+          .with_arg("score", result.score().to_string()),
+        font.clone(),
+      ));
+    })
+    .id()
+}
@@ -1,30 +1,459 @@
-use bevy::window::PresentMode;
+use bevy::window::{MonitorSelection, PresentMode, PrimaryWindow, WindowMode};
+use enum_iterator::Sequence;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
-use crate::prelude::*;
+use crate::{input::InputMap, prelude::*, storage};
 
-#[derive(Reflect)]
+/// Version of the settings file layout, bumped by changes that
+/// `#[serde(default)]` can't handle on its own.
+const SETTINGS_VERSION: u32 = 1;
+const SETTINGS_FILE: &str = "settings.ron";
+/// Range of [`Settings::ui_scale`].
+pub const UI_SCALE_RANGE: (f32, f32) = (0.5, 2.0);
+/// Range of [`Settings::game_speed`].
+pub const GAME_SPEED_RANGE: (f32, f32) = (0.5, 1.0);
+/// Range of the volume levels and of [`Settings::screen_shake`].
+const LEVEL_RANGE: (f32, f32) = (0.0, 1.0);
+/// Seconds without changes before the settings are written, so dragging a
+/// slider or moving the window doesn't write them on every frame.
+const SAVE_DELAY: f32 = 0.5;
+
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Hash,
+  Reflect,
+  Sequence,
+  Serialize,
+  Deserialize
+)]
 pub enum Language {
   English,
+  Italian,
+}
+
+impl Language {
+  /// Name of the string table file under `assets/locales/`.
+  pub fn code(self) -> &'static str {
+    match self {
+      Self::English => "en",
+      Self::Italian => "it",
+    }
+  }
+}
+
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Reflect,
+  Sequence,
+  Serialize,
+  Deserialize
+)]
+pub enum DisplayMode {
+  Windowed,
+  Borderless,
+  Fullscreen,
+}
+
+impl DisplayMode {
+  pub fn window_mode(self) -> WindowMode {
+    match self {
+      Self::Windowed => WindowMode::Windowed,
+      Self::Borderless => {
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+      }
+      Self::Fullscreen => WindowMode::Fullscreen(MonitorSelection::Current),
+    }
+  }
+
+  /// Key of the name of the mode in the string tables.
+  pub fn text_key(self) -> &'static str {
+    match self {
+      Self::Windowed => "display_mode.windowed",
+      Self::Borderless => "display_mode.borderless",
+      Self::Fullscreen => "display_mode.fullscreen",
+    }
+  }
+}
+
+/// Sizes the window can be set to from the settings.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Reflect,
+  Sequence,
+  Serialize,
+  Deserialize
+)]
+pub enum Resolution {
+  Hd,
+  HdPlus,
+  FullHd,
+  Qhd,
+}
+
+// Windows and frames are handled by the browser on the web.
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+impl Resolution {
+  /// Logical size of the window.
+  pub fn size(self) -> UVec2 {
+    match self {
+      Self::Hd => UVec2::new(1280, 720),
+      Self::HdPlus => UVec2::new(1600, 900),
+      Self::FullHd => UVec2::new(1920, 1080),
+      Self::Qhd => UVec2::new(2560, 1440),
+    }
+  }
+
+  pub fn text_key(self) -> String {
+    let size = self.size();
+    format!("resolution.{}x{}", size.x, size.y)
+  }
+}
+
+/// The highest frame rate the game runs at.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Reflect,
+  Sequence,
+  Serialize,
+  Deserialize
+)]
+pub enum FrameCap {
+  Fps30,
+  Fps60,
+  Fps120,
+  Fps144,
+  Unlimited,
+}
+
+// Windows and frames are handled by the browser on the web.
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+impl FrameCap {
+  pub fn fps(self) -> Option<u32> {
+    match self {
+      Self::Fps30 => Some(30),
+      Self::Fps60 => Some(60),
+      Self::Fps120 => Some(120),
+      Self::Fps144 => Some(144),
+      Self::Unlimited => None,
+    }
+  }
+
+  pub fn text_key(self) -> String {
+    match self.fps() {
+      Some(fps) => format!("frame_cap.{fps}"),
+      None => "frame_cap.unlimited".to_string(),
+    }
+  }
 }
 
-#[derive(Resource, Reflect)]
+/// Presets for the costly graphics features, ordered from the cheapest.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  PartialOrd,
+  Ord,
+  Reflect,
+  Sequence,
+  Serialize,
+  Deserialize
+)]
+pub enum Quality {
+  Low,
+  Medium,
+  High,
+}
+
+impl Quality {
+  /// Fraction of the particles spawned by effects.
+  pub fn particle_density(self) -> f32 {
+    match self {
+      Self::Low => 0.25,
+      Self::Medium => 0.5,
+      Self::High => 1.0,
+    }
+  }
+
+  pub fn text_key(self) -> &'static str {
+    match self {
+      Self::Low => "quality.low",
+      Self::Medium => "quality.medium",
+      Self::High => "quality.high",
+    }
+  }
+}
+
+/// Colors of the attacks, with palettes that stay readable with the common
+/// kinds of color blindness.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Reflect,
+  Sequence,
+  Serialize,
+  Deserialize
+)]
+pub enum Palette {
+  Standard,
+  Deuteranopia,
+  Protanopia,
+  Tritanopia,
+}
+
+impl Palette {
+  /// Color of the area covered by a telegraph.
+  pub fn telegraph(self) -> Color {
+    match self {
+      Self::Standard => Color::srgb(1.0, 0.2, 0.2),
+      Self::Deuteranopia => Color::srgb_u8(0xe6, 0x9f, 0x00),
+      Self::Protanopia => Color::srgb_u8(0xf0, 0xe4, 0x42),
+      Self::Tritanopia => Color::srgb_u8(0xd5, 0x5e, 0x00),
+    }
+  }
+
+  pub fn projectile(self) -> Color {
+    match self {
+      Self::Standard => Color::srgb(1.0, 0.35, 0.35),
+      Self::Deuteranopia | Self::Protanopia => Color::srgb_u8(0x56, 0xb4, 0xe9),
+      Self::Tritanopia => Color::srgb_u8(0xcc, 0x79, 0xa7),
+    }
+  }
+
+  pub fn text_key(self) -> &'static str {
+    match self {
+      Self::Standard => "palette.standard",
+      Self::Deuteranopia => "palette.deuteranopia",
+      Self::Protanopia => "palette.protanopia",
+      Self::Tritanopia => "palette.tritanopia",
+    }
+  }
+}
+
+/// The clock the run timer follows.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Hash,
+  Reflect,
+  Sequence,
+  Serialize,
+  Deserialize
+)]
+pub enum TimingMethod {
+  /// Virtual time, which stops while the game is paused.
+  GameTime,
+  /// Wall clock time, pauses included.
+  RealTime,
+}
+
+impl TimingMethod {
+  pub fn text_key(self) -> &'static str {
+    match self {
+      Self::GameTime => "timing.game_time",
+      Self::RealTime => "timing.real_time",
+    }
+  }
+}
+
+/// Where the window was and how big it was the last time it was windowed,
+/// restored on the next start.
+#[derive(Debug, Default, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct WindowPlacement {
+  /// Position in physical pixels.
+  pub position: Option<IVec2>,
+  /// Logical size, which can differ from [`Settings::resolution`] once the
+  /// window gets resized by hand.
+  pub size: Option<Vec2>,
+}
+
+#[derive(Resource, Reflect, Serialize, Deserialize)]
 #[reflect(Resource)]
+#[serde(default)]
 pub struct Settings {
   pub vsync_enabled: bool,
+  pub display_mode: DisplayMode,
+  pub resolution: Resolution,
+  pub ui_scale: f32,
+  pub window: WindowPlacement,
+  pub frame_cap: FrameCap,
+  pub quality: Quality,
+  /// Relative speed of the game, slowed down to make fights easier to
+  /// follow.
+  pub game_speed: f32,
+  /// Replaces blinking and bright flashes with steady effects.
+  pub reduced_flashing: bool,
+  /// Multiplier of the screen shake, `0.0` turns it off.
+  pub screen_shake: f32,
+  pub palette: Palette,
+  pub timing: TimingMethod,
   pub music_level: f32,
   pub sound_level: f32,
   pub language: Language,
+  pub controls: InputMap,
 }
 
 impl Default for Settings {
   fn default() -> Self {
     Self {
       vsync_enabled: true,
+      display_mode: DisplayMode::Windowed,
+      resolution: Resolution::Hd,
+      ui_scale: 1.0,
+      window: WindowPlacement::default(),
+      frame_cap: FrameCap::Unlimited,
+      quality: Quality::High,
+      game_speed: 1.0,
+      reduced_flashing: false,
+      screen_shake: 1.0,
+      palette: Palette::Standard,
+      timing: TimingMethod::GameTime,
       music_level: 1.0,
       sound_level: 1.0,
       language: Language::English,
+      controls: InputMap::default(),
+    }
+  }
+}
+
+/// The on-disk layout of [`Settings`].
+#[derive(Serialize)]
+struct SettingsFile<'a> {
+  version: u32,
+  settings: &'a Settings,
+}
+
+/// The part of the settings file read before knowing its layout.
+#[derive(Deserialize)]
+struct SettingsHeader {
+  version: u32,
+}
+
+#[derive(Deserialize)]
+struct VersionedSettings<T> {
+  settings: T,
+}
+
+#[derive(Debug, Error)]
+enum SettingsError {
+  #[error(transparent)]
+  Storage(#[from] storage::StorageError),
+  #[error("could not parse settings: {0}")]
+  Parse(#[from] ron::error::SpannedError),
+  #[error("could not serialize settings: {0}")]
+  Serialize(#[from] ron::Error),
+  #[error("settings version {0} is not supported")]
+  Version(u32),
+}
+
+impl Settings {
+  /// Loads the stored settings, falling back to the defaults if they are
+  /// missing or unreadable.
+  fn load() -> Self {
+    match Self::read() {
+      Ok(settings) => settings.unwrap_or_default(),
+      Err(error) => {
+        warn!("{error}, using the default settings");
+        Self::default()
+      }
     }
   }
+
+  fn read() -> Result<Option<Self>, SettingsError> {
+    let Some(contents) = storage::read(SETTINGS_FILE)? else {
+      return Ok(None);
+    };
+
+    let SettingsHeader { version } = ron::from_str(&contents)?;
+
+    // Files of older versions are read with their own layout and migrated
+    // here, one arm per version.
+    let settings = match version {
+      SETTINGS_VERSION => {
+        ron::from_str::<VersionedSettings<Self>>(&contents)?.settings
+      }
+      version => return Err(SettingsError::Version(version)),
+    };
+
+    Ok(Some(settings.sanitized()))
+  }
+
+  /// Brings values edited by hand back within the ranges the menus allow,
+  /// using the defaults for values that aren't numbers.
+  fn sanitized(mut self) -> Self {
+    let default = Self::default();
+
+    self.ui_scale = clamp_or(
+      self.ui_scale,
+      UI_SCALE_RANGE,
+      default.ui_scale,
+    );
+    self.game_speed = clamp_or(
+      self.game_speed,
+      GAME_SPEED_RANGE,
+      default.game_speed,
+    );
+    self.screen_shake = clamp_or(
+      self.screen_shake,
+      LEVEL_RANGE,
+      default.screen_shake,
+    );
+    self.music_level = clamp_or(
+      self.music_level,
+      LEVEL_RANGE,
+      default.music_level,
+    );
+    self.sound_level = clamp_or(
+      self.sound_level,
+      LEVEL_RANGE,
+      default.sound_level,
+    );
+
+    self
+  }
+
+  fn save(&self) -> Result<(), SettingsError> {
+    let file = SettingsFile {
+      version: SETTINGS_VERSION,
+      settings: self,
+    };
+
+    let contents = ron::ser::to_string_pretty(&file, Default::default())?;
+    storage::write(SETTINGS_FILE, &contents)?;
+
+    Ok(())
+  }
+}
+
+fn clamp_or(value: f32, (min, max): (f32, f32), default: f32) -> f32 {
+  match value.is_finite() {
+    true => value.clamp(min, max),
+    false => default,
+  }
 }
 
 pub struct SettingsPlugin;
@@ -32,21 +461,150 @@ pub struct SettingsPlugin;
 impl Plugin for SettingsPlugin {
   fn build(&self, app: &mut App) {
     app
-      .insert_resource(Settings::default())
+      // Loaded right away, so the settings already apply to the first frame.
+      .insert_resource(Settings::load())
+      .init_resource::<PendingSave>()
       .register_type::<Settings>()
-      .add_systems(Update, update_settings);
+      .add_systems(
+        Update,
+        (
+          update_settings,
+          #[cfg(not(target_arch = "wasm32"))]
+          remember_window_placement,
+          schedule_settings_save.run_if(
+            resource_changed::<Settings>.and(not(resource_added::<Settings>)),
+          ),
+        ),
+      )
+      // In `Last`, to see the exit requests sent during the frame.
+      .add_systems(Last, save_settings);
   }
 }
 
 fn update_settings(
   settings: Res<Settings>,
-  mut window_query: Query<&mut Window>,
+  mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+  mut ui_scale: ResMut<UiScale>,
+  mut time: ResMut<Time<Virtual>>,
+  #[cfg_attr(target_arch = "wasm32", allow(unused))]
+  mut applied_resolution: Local<Option<Resolution>>,
 ) {
-  if settings.is_changed() {
-    let mut window = window_query.single_mut();
-    window.present_mode = match settings.vsync_enabled {
-      true => PresentMode::AutoVsync,
-      false => PresentMode::AutoNoVsync,
+  if !settings.is_changed() {
+    return;
+  }
+
+  let mut window = window_query.single_mut();
+  window.present_mode = match settings.vsync_enabled {
+    true => PresentMode::AutoVsync,
+    false => PresentMode::AutoNoVsync,
+  };
+  window.mode = settings.display_mode.window_mode();
+
+  // On the web the canvas follows the size of the page instead.
+  #[cfg(not(target_arch = "wasm32"))]
+  if *applied_resolution != Some(settings.resolution) {
+    // Only a new resolution resizes the window, the first time around it
+    // goes back where it was left.
+    let size = match (*applied_resolution, &settings.window) {
+      (
+        None,
+        WindowPlacement {
+          size: Some(size), ..
+        },
+      ) => *size,
+      _ => settings.resolution.size().as_vec2(),
     };
+    window.resolution.set(size.x, size.y);
+
+    if let (None, Some(position)) = (
+      *applied_resolution,
+      settings.window.position,
+    ) {
+      window.position = WindowPosition::At(position);
+    }
+
+    *applied_resolution = Some(settings.resolution);
+  }
+
+  if ui_scale.0 != settings.ui_scale {
+    ui_scale.0 = settings.ui_scale;
+  }
+
+  if time.relative_speed() != settings.game_speed {
+    time.set_relative_speed(settings.game_speed);
+  }
+}
+
+/// Keeps track of the window being moved and resized, while it's windowed.
+#[cfg(not(target_arch = "wasm32"))]
+fn remember_window_placement(
+  mut moved_events: EventReader<WindowMoved>,
+  mut resized_events: EventReader<bevy::window::WindowResized>,
+  primary_window: Query<Entity, With<PrimaryWindow>>,
+  mut settings: ResMut<Settings>,
+) {
+  let Ok(primary_window) = primary_window.get_single() else {
+    return;
+  };
+
+  let position = moved_events
+    .read()
+    .filter(|event| event.window == primary_window)
+    .last()
+    .map(|event| event.position);
+
+  let size = resized_events
+    .read()
+    .filter(|event| event.window == primary_window)
+    .last()
+    .map(|event| Vec2::new(event.width, event.height));
+
+  if settings.display_mode != DisplayMode::Windowed {
+    return;
+  }
+
+  let placement = WindowPlacement {
+    position: position.or(settings.window.position),
+    size: size.or(settings.window.size),
+  };
+
+  if settings.window != placement {
+    settings.window = placement;
+  }
+}
+
+/// Counts down to writing changed settings, restarted by every change.
+#[derive(Resource, Default)]
+struct PendingSave(Option<Timer>);
+
+fn schedule_settings_save(mut pending: ResMut<PendingSave>) {
+  pending.0 = Some(Timer::from_seconds(
+    SAVE_DELAY,
+    TimerMode::Once,
+  ));
+}
+
+/// Writes the settings once the changes settle, or right away when the app
+/// is closing.
+fn save_settings(
+  settings: Res<Settings>,
+  time: Res<Time<Real>>,
+  mut exit_events: EventReader<AppExit>,
+  mut pending: ResMut<PendingSave>,
+) {
+  let exiting = exit_events.read().count() > 0;
+
+  let Some(timer) = pending.0.as_mut() else {
+    return;
+  };
+
+  if !timer.tick(time.delta()).finished() && !exiting {
+    return;
+  }
+
+  pending.0 = None;
+
+  if let Err(error) = settings.save() {
+    warn!("{error}");
   }
 }
@@ -9,9 +9,11 @@ impl Plugin for AppStatePlugin {
       .init_state::<AppState>()
       .add_sub_state::<MainMenuState>()
       .add_sub_state::<InGameState>()
+      .add_sub_state::<FightState>()
       .enable_state_scoped_entities::<AppState>()
       .enable_state_scoped_entities::<MainMenuState>()
-      .enable_state_scoped_entities::<InGameState>();
+      .enable_state_scoped_entities::<InGameState>()
+      .enable_state_scoped_entities::<FightState>();
   }
 }
 
@@ -36,6 +38,10 @@ pub enum AppState {
   /// In this state the application is displaying the game over screen.
   /// The user can restart the game or return to the main menu.
   GameOver,
+  /// The `victory` state of the application.
+  /// In this state the application is displaying the victory screen,
+  /// reached after every boss of the run has been defeated.
+  Victory,
 }
 
 // In this case, instead of deriving `States`, we derive `SubStates`
@@ -51,12 +57,32 @@ pub enum InGameState {
   Paused,
 }
 
+/// Progress of the current fight of a boss rush.
+/// It lives next to [`InGameState`], so pausing does not reset it.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, SubStates)]
+#[source(AppState = AppState::InGame)]
+pub enum FightState {
+  /// The boss has just been spawned and is being introduced.
+  #[default]
+  Intro,
+  /// The boss is attacking.
+  Fighting,
+  /// The boss has been defeated and the next one is about to come.
+  Intermission,
+}
+
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, SubStates)]
 #[source(AppState = AppState::MainMenu)]
 pub enum MainMenuState {
   #[default]
   MainScreen,
+  /// Choice of the difficulty, between the Play button and the run.
+  Difficulty,
   Settings,
+  /// Rebinding of the input actions, reached from [`MainMenuState::Settings`].
+  Controls,
+  /// Accessibility options, reached from [`MainMenuState::Settings`].
+  Accessibility,
   #[allow(unused)]
   Credits,
 }
@@ -0,0 +1,88 @@
+use thiserror::Error;
+
+/// Folder, or key prefix on the web, shared by every stored file.
+const APP_NAME: &str = "boss-rush-2025";
+
+#[derive(Debug, Error)]
+pub enum StorageError {
+  #[cfg(not(target_arch = "wasm32"))]
+  #[error("could not find the config directory")]
+  NoConfigDir,
+  #[cfg(not(target_arch = "wasm32"))]
+  #[error("could not access the storage: {0}")]
+  Io(#[from] std::io::Error),
+  #[cfg(target_arch = "wasm32")]
+  #[error("could not access the local storage")]
+  LocalStorage,
+}
+
+/// Reads a file kept between runs, or `None` if it was never written.
+/// Files live in the platform config directory on native, and in
+/// `localStorage` on the web.
+pub fn read(name: &str) -> Result<Option<String>, StorageError> {
+  backend::read(name)
+}
+
+/// Writes a stored file, replacing its previous contents.
+pub fn write(name: &str, contents: &str) -> Result<(), StorageError> {
+  backend::write(name, contents)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod backend {
+  use std::{fs, io, path::PathBuf};
+
+  use super::{StorageError, APP_NAME};
+
+  fn path(name: &str) -> Result<PathBuf, StorageError> {
+    let dir = dirs::config_dir().ok_or(StorageError::NoConfigDir)?;
+    Ok(dir.join(APP_NAME).join(name))
+  }
+
+  pub fn read(name: &str) -> Result<Option<String>, StorageError> {
+    match fs::read_to_string(path(name)?) {
+      Ok(contents) => Ok(Some(contents)),
+      Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+      Err(error) => Err(error.into()),
+    }
+  }
+
+  pub fn write(name: &str, contents: &str) -> Result<(), StorageError> {
+    let path = path(name)?;
+
+    if let Some(dir) = path.parent() {
+      fs::create_dir_all(dir)?;
+    }
+
+    Ok(fs::write(path, contents)?)
+  }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod backend {
+  use web_sys::Storage;
+
+  use super::{StorageError, APP_NAME};
+
+  fn local_storage() -> Result<Storage, StorageError> {
+    web_sys::window()
+      .and_then(|window| window.local_storage().ok().flatten())
+      .ok_or(StorageError::LocalStorage)
+  }
+
+  fn key(name: &str) -> String {
+    format!("{APP_NAME}/{name}")
+  }
+
+  pub fn read(name: &str) -> Result<Option<String>, StorageError> {
+    local_storage()?
+      .get_item(&key(name))
+      .map_err(|_| StorageError::LocalStorage)
+  }
+
+  pub fn write(name: &str, contents: &str) -> Result<(), StorageError> {
+    local_storage()?
+      .set_item(&key(name), contents)
+      .map_err(|_| StorageError::LocalStorage)
+  }
+}
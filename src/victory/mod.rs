@@ -0,0 +1,99 @@
+use crate::prelude::*;
+use crate::results::{spawn_run_result, RunResult};
+
+pub struct VictoryPlugin<S: States> {
+  pub state: S,
+}
+
+impl<S: States> Plugin for VictoryPlugin<S> {
+  fn build(&self, app: &mut App) {
+    app.add_systems(
+      OnEnter(self.state.clone()),
+      setup_victory,
+    );
+  }
+}
+
+fn setup_victory(
+  mut commands: Commands,
+  res: Res<assets::UiAssets>,
+  result: Res<RunResult>,
+) {
+  commands.spawn((StateScoped(AppState::Victory), Camera2d));
+
+  let container = commands
+    .spawn((
+      Name::new("VictoryContainer"),
+      StateScoped(AppState::Victory),
+      Node {
+        width: Val::Percent(100.),
+        height: Val::Percent(100.),
+        position_type: PositionType::Relative,
+        flex_direction: FlexDirection::Column,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        row_gap: Val::Px(32.),
+        ..Default::default()
+      },
+    ))
+    .id();
+
+  let title = commands
+    .spawn((
+      LocalizedText::new("victory.title"),
+      TextFont {
+        font: res.font.clone(),
+        font_size: 48.,
+        ..Default::default()
+      },
+    ))
+    .id();
+
+  let run_result = spawn_run_result(
+    &mut commands,
+    &TextFont {
+      font: res.font.clone(),
+      font_size: 24.,
+      ..Default::default()
+    },
+    &result,
+  );
+
+  let play_again = commands
+    .spawn((
+      Button,
+      ButtonAction::set_state(AppState::InGame),
+    ))
+    .with_children(|parent| {
+      parent.spawn((
+        LocalizedText::new("common.play_again"),
+        TextFont {
+          font: res.font.clone(),
+          font_size: 32.,
+          ..Default::default()
+        },
+      ));
+    })
+    .id();
+
+  let main_menu = commands
+    .spawn((
+      Button,
+      ButtonAction::set_state(AppState::MainMenu),
+    ))
+    .with_children(|parent| {
+      parent.spawn((
+        LocalizedText::new("common.main_menu"),
+        TextFont {
+          font: res.font.clone(),
+          font_size: 32.,
+          ..Default::default()
+        },
+      ));
+    })
+    .id();
+
+  commands
+    .entity(container)
+    .add_children(&[title, run_result, play_again, main_menu]);
+}
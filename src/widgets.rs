@@ -0,0 +1,402 @@
+use std::marker::PhantomData;
+
+use bevy::ui::RelativeCursorPosition;
+use enum_iterator::{next_cycle, previous_cycle, Sequence};
+
+use crate::{
+  navigation::{Adjustable, Focused, NavigationLock},
+  prelude::*,
+};
+
+const TRACK_COLOR: Color = Color::srgb(0.2, 0.2, 0.2);
+const FILL_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+
+/// Runs the widgets bound to the resource `R`.
+pub struct WidgetsPlugin<R: Resource>(PhantomData<R>);
+
+impl<R: Resource> Default for WidgetsPlugin<R> {
+  fn default() -> Self {
+    Self(PhantomData)
+  }
+}
+
+impl<R: Resource> Plugin for WidgetsPlugin<R> {
+  fn build(&self, app: &mut App) {
+    app.add_systems(
+      Update,
+      (
+        (
+          press_toggles::<R>,
+          press_selectors::<R>,
+          drag_sliders::<R>,
+          adjust_focused_widgets::<R>
+            .run_if(not(resource_exists::<NavigationLock>)),
+        ),
+        (
+          update_toggles::<R>,
+          update_sliders::<R>,
+          update_selectors::<R>,
+        ),
+      )
+        .chain()
+        .run_if(resource_exists::<R>),
+    );
+  }
+}
+
+/// A button flipping a boolean field of the resource `R`.
+#[derive(Component)]
+#[require(Button)]
+pub struct Toggle<R: Resource> {
+  get: fn(&R) -> bool,
+  set: fn(&mut R, bool),
+}
+
+impl<R: Resource> Toggle<R> {
+  pub fn new(get: fn(&R) -> bool, set: fn(&mut R, bool)) -> Self {
+    Self { get, set }
+  }
+}
+
+/// A horizontal bar for a field of `R` between `min` and `max`, `0.0` and `1.0`
+/// by default, dragged with the mouse or moved by `step` with the left and
+/// right actions. The value is shown as a percentage.
+#[derive(Component)]
+#[require(Button, Adjustable)]
+pub struct Slider<R: Resource> {
+  get: fn(&R) -> f32,
+  set: fn(&mut R, f32),
+  step: f32,
+  min: f32,
+  max: f32,
+}
+
+impl<R: Resource> Slider<R> {
+  pub fn new(get: fn(&R) -> f32, set: fn(&mut R, f32), step: f32) -> Self {
+    Self {
+      get,
+      set,
+      step,
+      min: 0.0,
+      max: 1.0,
+    }
+  }
+
+  pub fn with_range(mut self, min: f32, max: f32) -> Self {
+    self.min = min;
+    self.max = max;
+    self
+  }
+
+  /// Position of the value along the bar, between `0.0` and `1.0`.
+  fn fraction(&self, resource: &R) -> f32 {
+    ((self.get)(resource) - self.min) / (self.max - self.min)
+  }
+}
+
+/// The bar of a [`Slider`], which the mouse position is measured against.
+#[derive(Component)]
+struct SliderTrack;
+
+/// The filled part of a [`SliderTrack`].
+#[derive(Component)]
+struct SliderFill;
+
+#[derive(Component)]
+struct SliderText;
+
+/// A button cycling a field of `R` through the values of an enum, forwards when
+/// pressed and both ways with the left and right actions.
+#[derive(Component)]
+#[require(Button, Adjustable)]
+pub struct Selector<R: Resource> {
+  /// Key of the string of the current value.
+  text_key: Box<dyn Fn(&R) -> String + Send + Sync>,
+  cycle: Box<dyn Fn(&mut R, bool) + Send + Sync>,
+}
+
+impl<R: Resource> Selector<R> {
+  pub fn new<T: Sequence + Copy + Send + Sync + 'static>(
+    get: fn(&R) -> T,
+    set: fn(&mut R, T),
+    text_key: fn(T) -> String,
+  ) -> Self {
+    Self {
+      text_key: Box::new(move |resource| text_key(get(resource))),
+      cycle: Box::new(move |resource, forward| {
+        let current = get(resource);
+        let next = match forward {
+          true => next_cycle(&current),
+          false => previous_cycle(&current),
+        };
+        set(resource, next);
+      }),
+    }
+  }
+}
+
+/// Spawns a row with the name of a setting on the left and its widget on
+/// the right.
+pub fn spawn_setting_row(
+  parent: &mut ChildBuilder,
+  font: &TextFont,
+  label_key: &str,
+  spawn_widget: impl FnOnce(&mut ChildBuilder),
+) {
+  parent
+    .spawn(Node {
+      width: Val::Px(560.),
+      justify_content: JustifyContent::SpaceBetween,
+      align_items: AlignItems::Center,
+      ..Default::default()
+    })
+    .with_children(|row| {
+      row.spawn((
+        LocalizedText::new(label_key),
+        font.clone(),
+      ));
+      spawn_widget(row);
+    });
+}
+
+pub fn spawn_toggle<R: Resource>(
+  parent: &mut ChildBuilder,
+  font: &TextFont,
+  toggle: Toggle<R>,
+) {
+  parent.spawn(toggle).with_children(|button| {
+    button.spawn((LocalizedText::new(""), font.clone()));
+  });
+}
+
+pub fn spawn_slider<R: Resource>(
+  parent: &mut ChildBuilder,
+  font: &TextFont,
+  slider: Slider<R>,
+) {
+  parent
+    .spawn((
+      slider,
+      Node {
+        column_gap: Val::Px(16.),
+        align_items: AlignItems::Center,
+        ..Default::default()
+      },
+    ))
+    .with_children(|button| {
+      button
+        .spawn((
+          SliderTrack,
+          RelativeCursorPosition::default(),
+          Node {
+            width: Val::Px(160.),
+            height: Val::Px(12.),
+            ..Default::default()
+          },
+          BackgroundColor(TRACK_COLOR),
+        ))
+        .with_children(|track| {
+          track.spawn((
+            SliderFill,
+            Node {
+              height: Val::Percent(100.),
+              ..Default::default()
+            },
+            BackgroundColor(FILL_COLOR),
+          ));
+        });
+
+      button.spawn((
+        SliderText,
+        Text::default(),
+        font.clone(),
+        Node {
+          width: Val::Px(80.),
+          ..Default::default()
+        },
+      ));
+    });
+}
+
+pub fn spawn_selector<R: Resource>(
+  parent: &mut ChildBuilder,
+  font: &TextFont,
+  selector: Selector<R>,
+) {
+  parent.spawn(selector).with_children(|button| {
+    button.spawn((LocalizedText::new(""), font.clone()));
+  });
+}
+
+fn press_toggles<R: Resource>(
+  mut resource: ResMut<R>,
+  toggles: Query<(&Toggle<R>, &Interaction), Changed<Interaction>>,
+) {
+  for (toggle, interaction) in &toggles {
+    if *interaction == Interaction::Pressed {
+      let value = (toggle.get)(&resource);
+      (toggle.set)(&mut resource, !value);
+    }
+  }
+}
+
+fn press_selectors<R: Resource>(
+  mut resource: ResMut<R>,
+  selectors: Query<(&Selector<R>, &Interaction), Changed<Interaction>>,
+) {
+  for (selector, interaction) in &selectors {
+    if *interaction == Interaction::Pressed {
+      (selector.cycle)(&mut resource, true);
+    }
+  }
+}
+
+/// Moves pressed sliders to the mouse, for as long as the button is held.
+fn drag_sliders<R: Resource>(
+  mut resource: ResMut<R>,
+  mouse: Res<ButtonInput<MouseButton>>,
+  sliders: Query<(&Slider<R>, &Interaction)>,
+  tracks: Query<(&Parent, &RelativeCursorPosition), With<SliderTrack>>,
+) {
+  // Navigation presses buttons without the mouse.
+  if !mouse.pressed(MouseButton::Left) {
+    return;
+  }
+
+  for (parent, cursor) in &tracks {
+    let Ok((slider, interaction)) = sliders.get(parent.get()) else {
+      continue;
+    };
+
+    let Some(position) = cursor.normalized else {
+      continue;
+    };
+
+    if *interaction == Interaction::Pressed {
+      let value = slider.min + position.x * (slider.max - slider.min);
+      set_slider(&mut resource, slider, value);
+    }
+  }
+}
+
+fn adjust_focused_widgets<R: Resource>(
+  mut resource: ResMut<R>,
+  actions: Res<ActionState>,
+  widgets: Query<(Option<&Slider<R>>, Option<&Selector<R>>), With<Focused>>,
+) {
+  let forward = match (
+    actions.just_pressed(Action::MoveLeft),
+    actions.just_pressed(Action::MoveRight),
+  ) {
+    (true, false) => false,
+    (false, true) => true,
+    _ => return,
+  };
+
+  for (slider, selector) in &widgets {
+    if let Some(slider) = slider {
+      let step = if forward { slider.step } else { -slider.step };
+      // Snapped to the steps, so repeated presses don't drift.
+      let value = (slider.get)(&resource) + step;
+      let value = (value / slider.step).round() * slider.step;
+      set_slider(&mut resource, slider, value);
+    }
+
+    if let Some(selector) = selector {
+      (selector.cycle)(&mut resource, forward);
+    }
+  }
+}
+
+/// Only writes actual changes, so dragging in place does not mark the
+/// resource as changed.
+fn set_slider<R: Resource>(
+  resource: &mut ResMut<R>,
+  slider: &Slider<R>,
+  value: f32,
+) {
+  let value = value.clamp(slider.min, slider.max);
+
+  if (slider.get)(resource) != value {
+    (slider.set)(resource, value);
+  }
+}
+
+fn update_toggles<R: Resource>(
+  resource: Res<R>,
+  toggles: Query<(Ref<Toggle<R>>, &Children)>,
+  mut texts: Query<&mut LocalizedText>,
+) {
+  for (toggle, children) in &toggles {
+    if !resource.is_changed() && !toggle.is_added() {
+      continue;
+    }
+
+    let key = match (toggle.get)(&resource) {
+      true => "common.on",
+      false => "common.off",
+    };
+
+    let mut texts = texts.iter_many_mut(children);
+    while let Some(mut text) = texts.fetch_next() {
+      if text.key != key {
+        text.key = key.to_string();
+      }
+    }
+  }
+}
+
+fn update_sliders<R: Resource>(
+  resource: Res<R>,
+  sliders: Query<Ref<Slider<R>>>,
+  tracks: Query<&Parent, With<SliderTrack>>,
+  mut fills: Query<(&Parent, &mut Node), With<SliderFill>>,
+  mut texts: Query<(&Parent, &mut Text), With<SliderText>>,
+) {
+  let changed =
+    |slider: &Ref<Slider<R>>| resource.is_changed() || slider.is_added();
+
+  for (track, mut node) in &mut fills {
+    let Ok(slider) = tracks
+      .get(track.get())
+      .and_then(|parent| sliders.get(parent.get()))
+    else {
+      continue;
+    };
+
+    if changed(&slider) {
+      node.width = Val::Percent(slider.fraction(&resource) * 100.);
+    }
+  }
+
+  for (parent, mut text) in &mut texts {
+    let Ok(slider) = sliders.get(parent.get()) else {
+      continue;
+    };
+
+    if changed(&slider) {
+      text.0 = format!("{:.0}%", (slider.get)(&resource) * 100.);
+    }
+  }
+}
+
+fn update_selectors<R: Resource>(
+  resource: Res<R>,
+  selectors: Query<(Ref<Selector<R>>, &Children)>,
+  mut texts: Query<&mut LocalizedText>,
+) {
+  for (selector, children) in &selectors {
+    if !resource.is_changed() && !selector.is_added() {
+      continue;
+    }
+
+    let key = (selector.text_key)(&resource);
+
+    let mut texts = texts.iter_many_mut(children);
+    while let Some(mut text) = texts.fetch_next() {
+      if text.key != key {
+        text.key = key.clone();
+      }
+    }
+  }
+}